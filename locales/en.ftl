@@ -0,0 +1,415 @@
+# English messages. This is the reference catalog: every other language must define the same keys
+# with the same variables, and anything missing there falls back to the text here.
+
+## Shared
+
+not-available = N/A
+yes = Yes
+no = No
+and-more = ...and { $count } more
+page-previous = Previous
+page-next = Next
+page-of = Page { $page } of { $total }
+page-invalid = There's no page { $page }.
+not-aired-yet = TBA
+list-none = None
+
+## Dispatcher
+
+usage-line = { $problem } Usage: `{ $usage }`
+usage-missing = Missing <{ $arg }>.
+usage-invalid-mal-id = Invalid MAL ID. Please provide a numeric ID.
+usage-invalid-number = Invalid { $arg }. Please provide a number starting from 1.
+usage-unexpected = Unexpected `{ $text }`.
+admin-only = Only server admins (Administrator or Manage Server permission) can use `{ $usage }`.
+cooldown = Slow down! You can use `{ $command }` again in { $seconds }s.
+cooldown-guild = `{ $command }` was just used on this server. Try again in { $seconds }s.
+busy = I'm still working on your other commands. Try again when they're done.
+perms-role-denied = Your roles don't allow `{ $command }` on this server.
+channel-denied = I don't answer commands in this channel.
+
+## Embed fields
+
+field-english-title = English Title
+field-japanese-title = Japanese Title
+field-romanized-title = Romanized Title
+field-approved = Approved
+field-type = Type
+field-source = Source
+field-episodes = Episodes
+field-status = Status
+field-airing = Airing
+field-aired = Aired
+field-duration = Duration
+field-rating = Rating
+field-score = Score
+field-rank = Rank
+field-popularity = Popularity
+field-members = Members
+field-favorites = Favorites
+field-genres = Genres
+field-themes = Themes
+field-demographic = Demographic
+field-studios = Studios
+field-producers = Producers
+field-licensors = Licensors
+field-trailer = Trailer
+field-alternative-titles = Alternative Titles
+title-type-english = English
+title-type-japanese = Japanese
+title-type-german = German
+title-type-spanish = Spanish
+title-type-french = French
+title-type-synonym = Synonyms
+field-chapters = Chapters
+field-volumes = Volumes
+field-published = Published
+field-kanji-name = Kanji Name
+field-nicknames = Nicknames
+field-streaming = Streaming
+field-official-links = Official Links
+field-established = Established
+field-anime = Anime
+field-links = Links
+field-filler = Filler
+field-recap = Recap
+field-watching = Watching
+field-completed = Completed
+field-on-hold = On Hold
+field-dropped = Dropped
+field-plan-to-watch = Plan to Watch
+field-total = Total
+field-date = Date
+field-author = Author
+field-comments = Comments
+field-tags = Tags
+field-episodes-watched = Episodes Watched
+field-promos = Promos
+field-music-videos = Music Videos
+field-openings = Openings
+field-endings = Endings
+field-continued = { $field } (continued)
+
+## Anime details and recommendations
+
+anime-not-found = No anime found for '{ $query }'.
+anime-ambiguous = '{ $query }' matches several anime. Which one did you mean?
+anime-pick-placeholder = Pick the anime you meant
+anime-pick-not-yours = Only the person who ran the command can pick from this menu.
+details-not-found = Could not find details for MAL ID: { $id }. It might not exist or an error occurred.
+details-aired-from = From: { $date }
+details-aired-to = To: { $date }
+details-score-by = { $score }/10 (by { $users } users)
+streaming-region-note = Streaming availability differs by region; platforms are listed globally.
+links-and-more = and { $count } more
+adult-blocked = Sorry, { $title } is an adult-only title, so I can't show it in this channel.
+recommendations-title = Recommendations for MAL ID: { $id }
+recommendations-none = No recommendations found for MAL ID: { $id }.
+recommendations-error = An error occurred while fetching recommendations for MAL ID: { $id }. Please try again later.
+
+## Search
+
+search-no-results = No results found for '{ $query }'.
+search-title = Search results for '{ $query }'
+search-line = - MAL ID: { $id }, Title: { $title }
+search-footer = To get more details, type `{ $prefix }anime details <MAL_ID>`
+search-error = An error occurred while searching for anime. Please try again later.
+
+## Random
+
+random-invalid-score = Invalid minimum score: `{ $value }`.
+random-unknown-option = Unknown option: `{ $option }`.
+random-anime-error = An error occurred while rolling a random anime. Please try again later.
+random-manga-error = An error occurred while rolling a random manga. Please try again later.
+random-character-error = An error occurred while rolling a random character. Please try again later.
+random-no-match = Nothing matched your filters after { $rolls } rolls. Try loosening them or roll again.
+random-reroll = Re-roll
+
+## Genres
+
+genres-heading-genres = Genres
+genres-heading-themes = Themes
+genres-heading-demographics = Demographics
+genres-error = An error occurred while fetching the genre list. Please try again later.
+genres-title = Anime Genres
+genres-footer = To list top shows in a genre, type `{ $prefix }genre <name>`
+genre-no-match = No genre matches '{ $query }'. Type `{ $prefix }genres` to see the full list.
+genre-title = Top { $genre } Anime
+genre-footer = { $count } entries in this genre. To get more details, type `{ $prefix }anime details <MAL_ID>`
+genre-empty = No anime found for genre '{ $genre }'.
+genre-error = An error occurred while searching the genre. Please try again later.
+
+## Studios
+
+studio-fetch-error = Could not fetch studio details for ID { $id }.
+studio-anime-error = Could not fetch anime for studio '{ $name }'.
+studio-page-empty = No anime found on this page.
+studio-anime-by-score = Anime by Score
+studio-not-found = No studio found for '{ $query }'.
+studio-search-error = An error occurred while searching for the studio. Please try again later.
+
+## Related entries and watch order
+
+related-none = No related entries found for MAL ID: { $id }.
+related-title = Related entries for MAL ID: { $id }
+related-footer = For a full franchise watch order, type `{ $prefix }anime watchorder <MAL_ID>`
+related-error = An error occurred while fetching related entries for MAL ID: { $id }. Please try again later.
+watchorder-building = Building the franchise watch order for MAL ID: { $id }. This can take a while...
+watchorder-episodes = { $count } eps
+watchorder-footer = { $count } entries, ordered by air date.
+watchorder-incomplete = The franchise is larger than the search limit, so some entries may be missing.
+adult-entries-hidden = { $count } entries are hidden by this server's content policy.
+watchorder-title = Watch order starting from MAL ID: { $id }
+watchorder-error = Could not build a watch order for MAL ID: { $id }. It might not exist or an error occurred.
+
+## Episodes
+
+episodes-error = An error occurred while fetching episodes for MAL ID: { $id }. Please try again later.
+episodes-none = No episodes found on page { $page } for MAL ID: { $id }.
+episodes-filler-tag = [Filler]
+episodes-recap-tag = [Recap]
+episodes-title = Episodes for MAL ID: { $id }
+episodes-footer = Page { $page } - { $filler } filler, { $recap } recap on this page. For one episode's synopsis, type `{ $prefix }anime episode <MAL_ID> <episode>`
+episode-title = Episode { $number }: { $title }
+episode-minutes = { $minutes } min
+episode-not-found = Could not find episode { $episode } for MAL ID: { $id }. It might not exist or an error occurred.
+
+## Where to watch
+
+where-title = Where to watch { $title } (MAL ID: { $id })
+where-no-streaming = No streaming platforms are listed for this anime.
+
+## Statistics
+
+stats-spread = Mean vote { $mean }, standard deviation { $spread }.
+stats-consensus = Consensus: most people scored it about the same.
+stats-divisive = Divisive: people strongly disagree about this one.
+stats-mixed = Mixed: opinions are moderately spread out.
+stats-not-found = Could not find statistics for MAL ID: { $id }. It might not exist or an error occurred.
+stats-title = Statistics for { $title }
+
+## Reviews
+
+reviews-error = An error occurred while fetching reviews for MAL ID: { $id }. Please try again later.
+reviews-none = No reviews found for MAL ID: { $id }.
+review-preliminary = Preliminary
+review-spoilers = Contains Spoilers
+review-title = Review of { $title }
+review-footer = Review { $number }
+review-full-button = Read full review (DM)
+review-dm-header = **Review by { $user } for MAL ID: { $id }**
+review-dm-sent = Sent the full review to your DMs.
+review-dm-failed = I couldn't send you a DM. Please check that direct messages from server members are allowed.
+
+## News
+
+news-none = No news found for MAL ID: { $id }.
+news-title = Latest news for MAL ID: { $id }
+news-by = by { $author }
+news-read-more = Read more
+news-footer = Admins can get new articles posted automatically with `{ $prefix }news subscribe <MAL_ID>`
+news-error = An error occurred while fetching news for MAL ID: { $id }. Please try again later.
+news-feed-footer = News for MAL ID: { $id }
+news-usage = Usage: `{ $prefix }news subscribe <MAL_ID> [MAL_ID...]`, `{ $prefix }news unsubscribe <MAL_ID...|all>` or `{ $prefix }news list`
+news-guild-only = News subscriptions can only be managed inside a server.
+news-already-subscribed = This channel is already subscribed to those anime.
+news-subscribed = Subscribed this channel to news for MAL IDs: { $ids }. New articles will be posted here; existing ones are skipped.
+news-invalid-id = Invalid MAL ID. Please provide numeric IDs.
+news-removed = { $count ->
+    [one] Removed { $count } news subscription from this channel.
+   *[other] Removed { $count } news subscriptions from this channel.
+}
+news-list-empty = No channel in this server is subscribed to anime news.
+news-list-title = News subscriptions
+
+## Pictures, videos and theme songs
+
+pictures-error = An error occurred while fetching pictures for MAL ID: { $id }. Please try again later.
+pictures-none = No pictures found for MAL ID: { $id }.
+pictures-title = Pictures for MAL ID: { $id }
+pictures-footer = Picture { $number } of { $total }
+videos-error = An error occurred while fetching videos for MAL ID: { $id }. Please try again later.
+videos-none = No promos or music videos found for MAL ID: { $id }.
+videos-title = Videos for MAL ID: { $id }
+videos-song-by = { $title } by { $author }
+themes-error = An error occurred while fetching theme songs for MAL ID: { $id }. Please try again later.
+themes-none = No opening or ending themes found for MAL ID: { $id }.
+themes-title = Theme songs of { $title }
+themes-song-by = by { $artist }
+themes-truncated = Some songs were left out to fit Discord's embed size limit.
+
+## MyAnimeList profiles
+
+profile-title = { $user }'s MyAnimeList Profile
+profile-joined = Joined { $date }
+profile-last-online = Last online { $date }
+profile-location = From { $location }
+profile-anime-stats = Anime Stats
+profile-anime-stats-body =
+    Days watched: { $days }
+    Mean score: { $mean }
+    Watching: { $watching }
+    Completed: { $completed }
+    On hold: { $on_hold }
+    Dropped: { $dropped }
+    Plan to watch: { $planned }
+    Total entries: { $total }
+    Episodes: { $episodes }
+profile-manga-stats = Manga Stats
+profile-manga-stats-body =
+    Days read: { $days }
+    Mean score: { $mean }
+    Reading: { $reading }
+    Completed: { $completed }
+    On hold: { $on_hold }
+    Dropped: { $dropped }
+    Plan to read: { $planned }
+    Total entries: { $total }
+    Chapters: { $chapters }
+profile-favorite-anime = Favorite Anime
+profile-favorite-manga = Favorite Manga
+profile-favorite-characters = Favorite Characters
+profile-anime-updates = Recent Anime Updates
+profile-manga-updates = Recent Manga Updates
+profile-update-default = Updated
+profile-update-scored = scored { $score }
+profile-invalid-username = '{ $user }' is not a valid MyAnimeList username.
+profile-not-found = Could not find the MyAnimeList user '{ $user }'. The profile might not exist or an error occurred.
+user-not-found = Could not find the MyAnimeList user '{ $user }'.
+mal-not-linked = You haven't linked a MyAnimeList account yet. Use `{ $prefix }mal link <username>` first, or look someone up with `{ $prefix }mal <username>`.
+mal-linked = Linked your Discord account to the MyAnimeList user '{ $user }'. Type `{ $prefix }mal` to see your profile.
+mal-compare-not-linked = Link your MyAnimeList account with `{ $prefix }mal link <username>` to compare yourself, or name two users: `{ $prefix }mal compare <user1> <user2>`.
+mal-unlinked = Unlinked your MyAnimeList account.
+mal-no-link = You don't have a linked MyAnimeList account.
+
+## Comparing users
+
+compare-compatibility = Compatibility: { $bar }
+compare-no-data = Neither profile has public favorites or statistics to compare.
+compare-user-stats = { $user }'s Anime Stats
+compare-stats-body =
+    Mean score: { $mean }
+    Days watched: { $days }
+    Completed: { $completed }
+    Dropped: { $dropped }
+compare-shared-anime = Shared Favorite Anime
+compare-shared-manga = Shared Favorite Manga
+compare-shared-characters = Shared Favorite Characters
+compare-only = Only { $user }
+compare-same-user = Pick two different users to compare.
+
+## Comparing anime
+
+anime-compare-wins = **{ $title }** wins { $count } of 4 ranked metrics.
+anime-compare-tie = It's a tie on the ranked metrics.
+anime-compare-usage = Usage: `{ $prefix }anime compare <id|name> <id|name>`. Separate names with `vs`, e.g. `{ $prefix }anime compare Naruto vs Bleach`.
+anime-compare-ambiguous = '{ $query }' matches several anime. Use a MAL ID instead:
+
+## Help
+
+help-title = Ferivonus Anime Bot Help
+help-intro = Hello! I'm an anime bot. Here are the command categories; use the buttons to browse them.
+help-footer = Page { $page }/{ $total } - Type { $prefix }help <command> for details on a command
+help-category-title = Help: { $category }
+help-optional = (optional)
+help-arguments = Arguments
+help-examples = Examples
+help-aliases = Aliases
+help-cooldown = Cooldown
+help-cooldown-guild = { $user }s per user, { $guild }s per server
+help-permission = Permission
+help-admins-only = Server admins only
+help-heavy-command = Everyone, unless the server limits it to some roles
+help-unknown-command = Unknown command `{ $command }`. Type `{ $prefix }help` to see all commands.
+
+category-anime = Search & Details
+category-media = Episodes, Media & Community
+category-discover = Discover
+category-myanimelist = MyAnimeList Profiles
+category-server = Server Management
+category-general = General
+
+## Server settings and language
+
+config-title = Server Settings
+config-footer = Change a setting with { $prefix }config set <key> <value>
+config-updated = Updated `{ $key }` to { $value }.
+setting-error-prefix = The prefix must be 1 to { $max } characters without spaces.
+setting-error-language = Supported languages: { $languages }.
+setting-error-nsfw = The NSFW policy must be `age_restricted`, `block` or `allow`.
+setting-error-channels = List channels as #mentions or IDs, or use `all`.
+setting-error-denied-channels = List channels as #mentions or IDs, or use `none`.
+setting-error-roles = List roles as @mentions or IDs, or use `all`.
+setting-error-denied-roles = List roles as @mentions or IDs, or use `none`.
+setting-error-color = The embed color must be a hex color like `#5865F2`, or `default`.
+setting-error-cooldown = Use `<command> <seconds per user> [seconds per server]` with at most 3600 seconds, or `<command> default`.
+setting-error-unknown = Unknown setting `{ $key }`. Settings: { $keys }.
+perms-title = Command Permissions
+perms-description = Commands are answered in the allowed channels unless a channel is also denied. Heavy commands ({ $commands }) can only be used by the allowed roles, unless a member also has a denied role. Admins can always use them.
+perms-footer = Change a list with { $prefix }perms allow|deny channels|roles <targets>
+perms-error-list = `{ $list }` isn't a list. Use `channels` or `roles`.
+language-name = English
+language-current = You're seeing the bot in { $language }. Change it with `{ $prefix }language <{ $languages }>`, or follow the server's language with `{ $prefix }language default`.
+language-set = From now on I'll answer you in { $language }.
+language-reset = You'll see the bot in the server's language again.
+language-invalid = Supported languages: { $languages }, or `default` for the server's language.
+
+## Command help
+
+help-help = Lists the command categories, or explains a single command with its arguments and examples.
+help-language = Shows or changes the language the bot answers you in. `default` follows the server's language.
+help-anime-details = Fetches detailed information for a specific anime by its MyAnimeList ID or name. Ambiguous names show a menu to pick from.
+help-anime-recommendations = Fetches anime recommendations based on a given MyAnimeList ID or name.
+help-anime-related = Lists sequels, prequels, side stories, adaptations and spin-offs.
+help-anime-watchorder = Builds a chronological watch order for the whole franchise.
+help-anime-episodes = Lists episodes with air dates, scores and filler/recap flags.
+help-anime-episode = Shows a single episode's synopsis.
+help-anime-where = Lists streaming platforms and official links.
+help-anime-stats = Shows list statistics and a score distribution chart.
+help-anime-reviews = Browses user reviews; spoilers are hidden and the full text can be sent to your DMs.
+help-anime-news = Shows the latest news articles for an anime.
+help-anime-pictures = Browses the anime's pictures in a gallery.
+help-anime-videos = Lists promotional videos and music videos.
+help-anime-themes = Lists opening and ending songs with YouTube and Spotify search links.
+help-anime-compare = Compares two anime side by side and highlights the winner of each ranked metric. Separate names with `vs`.
+help-news-subscribe = (Admins) Posts new articles for these anime in the current channel.
+help-news-unsubscribe = (Admins) Stops posting news for these anime, or for everything, in the current channel.
+help-news-list = (Admins) Lists this server's news subscriptions.
+help-random = Rolls a random entry, optionally re-rolling until it matches the filters: `anime`/`manga`/`character`, `sfw`, `score=<min>`, `genre=<name>`, `type=<type>`.
+help-genres = Lists anime genres, themes and demographics with entry counts.
+help-genre = Lists the top rated anime in a genre. Close spellings are matched too.
+help-studio = Shows a studio or producer with its anime sorted by score.
+help-mal-compare = Compares two MyAnimeList users' favorites and stats and rates their compatibility. With one name it compares against your linked account.
+help-mal-link = Links your MyAnimeList account so `!mal` shows your profile.
+help-mal-unlink = Unlinks your MyAnimeList account.
+help-mal = Shows a MyAnimeList profile with stats, favorites and recent updates. Without a username it shows your linked account.
+help-config-show = (Admins) Shows this server's bot settings.
+help-config-set = (Admins) Changes a setting: `prefix`, `language` (en, tr), `nsfw` (age_restricted, block, allow), `allowed_channels` (#channels or all), `embed_color` (#RRGGBB or default), `cooldowns` (<command> <seconds per user> [seconds per server], or <command> default).
+help-perms-show = (Admins) Shows which channels the bot answers in and which roles can use heavy commands.
+help-perms-allow = (Admins) Sets the channels the bot answers in, or the roles that can use heavy commands. `all` lifts the limit.
+help-perms-deny = (Admins) Sets the channels the bot ignores, or the roles that can't use heavy commands. `none` clears the list.
+help-lain = Get details about Serial Experiments Lain.
+help-lain-recommendations = Get recommendations based on Serial Experiments Lain.
+help-anime = Searches for anime and lists basic information.
+
+arg-command = Command to explain, e.g. anime details
+arg-language = en, tr, or default for the server's language
+arg-mal-id = MyAnimeList ID of the anime
+arg-anime = MyAnimeList ID or name of the anime
+arg-page = Page of the episode list
+arg-episode = Episode number
+arg-anime-pair = Two MAL IDs, or two names separated by vs
+arg-news-ids = MyAnimeList IDs to follow, separated by spaces
+arg-news-ids-or-all = MyAnimeList IDs to stop following, or all
+arg-random-filters = anime, manga or character, then sfw, score=<min>, genre=<name>, type=<type>
+arg-genre = Genre, theme or demographic name
+arg-studio = Studio or producer name
+arg-username = MyAnimeList username
+arg-second-username = Second username; defaults to your linked account
+arg-own-username = Your MyAnimeList username
+arg-username-or-linked = MyAnimeList username; defaults to your linked account
+arg-config-key = prefix, language, nsfw, allowed_channels, embed_color or cooldowns
+arg-config-value = New value for the setting
+arg-perms-list = channels or roles
+arg-perms-targets = #channels or @roles, all or none
+arg-search = Name to search for
@@ -0,0 +1,412 @@
+# Türkçe mesajlar. Anahtarlar ve değişkenler en.ftl ile birebir aynı olmalı; eksik bir anahtar
+# İngilizce metne düşer.
+
+## Ortak
+
+not-available = Yok
+yes = Evet
+no = Hayır
+and-more = ...ve { $count } tane daha
+page-previous = Önceki
+page-next = Sonraki
+page-of = Sayfa { $page } / { $total }
+page-invalid = { $page }. sayfa yok.
+not-aired-yet = Belirsiz
+list-none = Yok
+
+## Komut işleyici
+
+usage-line = { $problem } Kullanım: `{ $usage }`
+usage-missing = <{ $arg }> eksik.
+usage-invalid-mal-id = Geçersiz MAL ID. Lütfen sayısal bir ID girin.
+usage-invalid-number = Geçersiz { $arg }. Lütfen 1 veya daha büyük bir sayı girin.
+usage-unexpected = Beklenmeyen `{ $text }`.
+admin-only = `{ $usage }` komutunu yalnızca sunucu yöneticileri (Yönetici veya Sunucuyu Yönet yetkisi) kullanabilir.
+cooldown = Biraz yavaş! `{ $command }` komutunu { $seconds } sn sonra tekrar kullanabilirsin.
+cooldown-guild = `{ $command }` bu sunucuda az önce kullanıldı. { $seconds } sn sonra tekrar dene.
+busy = Hâlâ diğer komutların üzerinde çalışıyorum. Onlar bitince tekrar dene.
+perms-role-denied = Rollerin bu sunucuda `{ $command }` komutunu kullanmana izin vermiyor.
+channel-denied = Bu kanalda komutlara yanıt vermiyorum.
+
+## Embed alanları
+
+field-english-title = İngilizce Adı
+field-japanese-title = Japonca Adı
+field-romanized-title = Romaji Adı
+field-approved = Onaylı
+field-type = Tür
+field-source = Kaynak
+field-episodes = Bölümler
+field-status = Durum
+field-airing = Yayında
+field-aired = Yayın Tarihi
+field-duration = Süre
+field-rating = Yaş Sınırı
+field-score = Puan
+field-rank = Sıralama
+field-popularity = Popülerlik
+field-members = Üyeler
+field-favorites = Favoriler
+field-genres = Türler
+field-themes = Temalar
+field-demographic = Hedef Kitle
+field-studios = Stüdyolar
+field-producers = Yapımcılar
+field-licensors = Lisans Sahipleri
+field-trailer = Fragman
+field-alternative-titles = Alternatif Adlar
+title-type-english = İngilizce
+title-type-japanese = Japonca
+title-type-german = Almanca
+title-type-spanish = İspanyolca
+title-type-french = Fransızca
+title-type-synonym = Diğer adları
+field-chapters = Bölümler
+field-volumes = Ciltler
+field-published = Yayımlanma
+field-kanji-name = Kanji Adı
+field-nicknames = Lakaplar
+field-streaming = Yayın Platformları
+field-official-links = Resmi Bağlantılar
+field-established = Kuruluş
+field-anime = Anime
+field-links = Bağlantılar
+field-filler = Filler
+field-recap = Özet Bölüm
+field-watching = İzleyen
+field-completed = Tamamlayan
+field-on-hold = Beklemede
+field-dropped = Bırakan
+field-plan-to-watch = İzlemeyi Planlayan
+field-total = Toplam
+field-date = Tarih
+field-author = Yazar
+field-comments = Yorumlar
+field-tags = Etiketler
+field-episodes-watched = İzlenen Bölüm
+field-promos = Tanıtımlar
+field-music-videos = Müzik Videoları
+field-openings = Açılışlar
+field-endings = Kapanışlar
+field-continued = { $field } (devam)
+
+## Anime ayrıntıları ve öneriler
+
+anime-not-found = '{ $query }' için anime bulunamadı.
+anime-ambiguous = '{ $query }' birden fazla animeyle eşleşiyor. Hangisini kastettin?
+anime-pick-placeholder = Kastettiğin animeyi seç
+anime-pick-not-yours = Bu menüden yalnızca komutu kullanan kişi seçim yapabilir.
+details-not-found = MAL ID { $id } için ayrıntı bulunamadı. Böyle bir kayıt olmayabilir ya da bir hata oluştu.
+details-aired-from = Başlangıç: { $date }
+details-aired-to = Bitiş: { $date }
+details-score-by = { $score }/10 ({ $users } kullanıcı)
+streaming-region-note = Yayın platformları bölgeye göre değişir; liste tüm dünya için geçerlidir.
+links-and-more = ve { $count } tane daha
+adult-blocked = Üzgünüm, { $title } yalnızca yetişkinlere yönelik bir yapım, bu yüzden bu kanalda gösteremiyorum.
+recommendations-title = MAL ID { $id } için öneriler
+recommendations-none = MAL ID { $id } için öneri bulunamadı.
+recommendations-error = MAL ID { $id } için öneriler getirilirken bir hata oluştu. Lütfen daha sonra tekrar dene.
+
+## Arama
+
+search-no-results = '{ $query }' için sonuç bulunamadı.
+search-title = '{ $query }' için arama sonuçları
+search-line = - MAL ID: { $id }, Adı: { $title }
+search-footer = Ayrıntılar için `{ $prefix }anime details <MAL_ID>` yaz
+search-error = Anime aranırken bir hata oluştu. Lütfen daha sonra tekrar dene.
+
+## Rastgele
+
+random-invalid-score = Geçersiz en düşük puan: `{ $value }`.
+random-unknown-option = Bilinmeyen seçenek: `{ $option }`.
+random-anime-error = Rastgele anime seçilirken bir hata oluştu. Lütfen daha sonra tekrar dene.
+random-manga-error = Rastgele manga seçilirken bir hata oluştu. Lütfen daha sonra tekrar dene.
+random-character-error = Rastgele karakter seçilirken bir hata oluştu. Lütfen daha sonra tekrar dene.
+random-no-match = { $rolls } denemede filtrelere uyan bir şey çıkmadı. Filtreleri gevşetmeyi ya da tekrar denemeyi dene.
+random-reroll = Tekrar Çek
+
+## Türler
+
+genres-heading-genres = Türler
+genres-heading-themes = Temalar
+genres-heading-demographics = Hedef Kitleler
+genres-error = Tür listesi getirilirken bir hata oluştu. Lütfen daha sonra tekrar dene.
+genres-title = Anime Türleri
+genres-footer = Bir türün en iyi animelerini görmek için `{ $prefix }genre <ad>` yaz
+genre-no-match = '{ $query }' ile eşleşen bir tür yok. Tam liste için `{ $prefix }genres` yaz.
+genre-title = En İyi { $genre } Animeleri
+genre-footer = Bu türde { $count } kayıt var. Ayrıntılar için `{ $prefix }anime details <MAL_ID>` yaz
+genre-empty = '{ $genre }' türünde anime bulunamadı.
+genre-error = Tür aranırken bir hata oluştu. Lütfen daha sonra tekrar dene.
+
+## Stüdyolar
+
+studio-fetch-error = { $id } ID'li stüdyonun ayrıntıları getirilemedi.
+studio-anime-error = '{ $name }' stüdyosunun animeleri getirilemedi.
+studio-page-empty = Bu sayfada anime yok.
+studio-anime-by-score = Puana Göre Animeler
+studio-not-found = '{ $query }' için stüdyo bulunamadı.
+studio-search-error = Stüdyo aranırken bir hata oluştu. Lütfen daha sonra tekrar dene.
+
+## İlgili kayıtlar ve izleme sırası
+
+related-none = MAL ID { $id } için ilgili kayıt bulunamadı.
+related-title = MAL ID { $id } ile ilgili kayıtlar
+related-footer = Serinin tam izleme sırası için `{ $prefix }anime watchorder <MAL_ID>` yaz
+related-error = MAL ID { $id } için ilgili kayıtlar getirilirken bir hata oluştu. Lütfen daha sonra tekrar dene.
+watchorder-building = MAL ID { $id } için serinin izleme sırası hazırlanıyor. Bu biraz sürebilir...
+watchorder-episodes = { $count } bölüm
+watchorder-footer = Yayın tarihine göre sıralı { $count } kayıt.
+watchorder-incomplete = Seri arama sınırından büyük, bu yüzden bazı kayıtlar eksik olabilir.
+adult-entries-hidden = Bu sunucunun içerik politikası nedeniyle { $count } kayıt gizlendi.
+watchorder-title = MAL ID { $id } ile başlayan izleme sırası
+watchorder-error = MAL ID { $id } için izleme sırası hazırlanamadı. Böyle bir kayıt olmayabilir ya da bir hata oluştu.
+
+## Bölümler
+
+episodes-error = MAL ID { $id } için bölümler getirilirken bir hata oluştu. Lütfen daha sonra tekrar dene.
+episodes-none = MAL ID { $id } için { $page }. sayfada bölüm bulunamadı.
+episodes-filler-tag = [Filler]
+episodes-recap-tag = [Özet]
+episodes-title = MAL ID { $id } bölümleri
+episodes-footer = Sayfa { $page } - bu sayfada { $filler } filler, { $recap } özet bölüm var. Bir bölümün özeti için `{ $prefix }anime episode <MAL_ID> <episode>` yaz
+episode-title = { $number }. Bölüm: { $title }
+episode-minutes = { $minutes } dk
+episode-not-found = MAL ID { $id } için { $episode }. bölüm bulunamadı. Böyle bir bölüm olmayabilir ya da bir hata oluştu.
+
+## Nerede izlenir
+
+where-title = { $title } nerede izlenir (MAL ID: { $id })
+where-no-streaming = Bu anime için listelenmiş bir yayın platformu yok.
+
+## İstatistikler
+
+stats-spread = Ortalama oy { $mean }, standart sapma { $spread }.
+stats-consensus = Uzlaşı: çoğu kişi benzer puan vermiş.
+stats-divisive = Tartışmalı: izleyiciler bu konuda hiç anlaşamıyor.
+stats-mixed = Karışık: görüşler orta derecede dağınık.
+stats-not-found = MAL ID { $id } için istatistik bulunamadı. Böyle bir kayıt olmayabilir ya da bir hata oluştu.
+stats-title = { $title } istatistikleri
+
+## İncelemeler
+
+reviews-error = MAL ID { $id } için incelemeler getirilirken bir hata oluştu. Lütfen daha sonra tekrar dene.
+reviews-none = MAL ID { $id } için inceleme bulunamadı.
+review-preliminary = Ön İnceleme
+review-spoilers = Spoiler İçerir
+review-title = { $title } incelemesi
+review-footer = İnceleme { $number }
+review-full-button = İncelemenin tamamını oku (DM)
+review-dm-header = **{ $user } tarafından MAL ID { $id } için yazılan inceleme**
+review-dm-sent = İncelemenin tamamı DM olarak gönderildi.
+review-dm-failed = Sana DM gönderemedim. Lütfen sunucu üyelerinden gelen özel mesajlara izin verdiğinden emin ol.
+
+## Haberler
+
+news-none = MAL ID { $id } için haber bulunamadı.
+news-title = MAL ID { $id } için son haberler
+news-by = yazan: { $author }
+news-read-more = Devamını oku
+news-footer = Yöneticiler yeni haberlerin otomatik paylaşılması için `{ $prefix }news subscribe <MAL_ID>` kullanabilir
+news-error = MAL ID { $id } için haberler getirilirken bir hata oluştu. Lütfen daha sonra tekrar dene.
+news-feed-footer = MAL ID { $id } haberleri
+news-usage = Kullanım: `{ $prefix }news subscribe <MAL_ID> [MAL_ID...]`, `{ $prefix }news unsubscribe <MAL_ID...|all>` veya `{ $prefix }news list`
+news-guild-only = Haber abonelikleri yalnızca bir sunucu içinde yönetilebilir.
+news-already-subscribed = Bu kanal zaten bu animelerin haberlerine abone.
+news-subscribed = Bu kanal şu MAL ID'lerin haberlerine abone oldu: { $ids }. Yeni haberler burada paylaşılacak; mevcut olanlar atlanır.
+news-invalid-id = Geçersiz MAL ID. Lütfen sayısal ID'ler girin.
+news-removed = Bu kanaldan { $count } haber aboneliği kaldırıldı.
+news-list-empty = Bu sunucuda anime haberlerine abone olan kanal yok.
+news-list-title = Haber abonelikleri
+
+## Resimler, videolar ve müzikler
+
+pictures-error = MAL ID { $id } için resimler getirilirken bir hata oluştu. Lütfen daha sonra tekrar dene.
+pictures-none = MAL ID { $id } için resim bulunamadı.
+pictures-title = MAL ID { $id } resimleri
+pictures-footer = Resim { $number } / { $total }
+videos-error = MAL ID { $id } için videolar getirilirken bir hata oluştu. Lütfen daha sonra tekrar dene.
+videos-none = MAL ID { $id } için tanıtım ya da müzik videosu bulunamadı.
+videos-title = MAL ID { $id } videoları
+videos-song-by = { $title } - { $author }
+themes-error = MAL ID { $id } için müzikler getirilirken bir hata oluştu. Lütfen daha sonra tekrar dene.
+themes-none = MAL ID { $id } için açılış ya da kapanış müziği bulunamadı.
+themes-title = { $title } müzikleri
+themes-song-by = - { $artist }
+themes-truncated = Discord'un embed boyutu sınırı yüzünden bazı şarkılar listeye sığmadı.
+
+## MyAnimeList profilleri
+
+profile-title = { $user } adlı kullanıcının MyAnimeList profili
+profile-joined = Katılım: { $date }
+profile-last-online = Son görülme: { $date }
+profile-location = Konum: { $location }
+profile-anime-stats = Anime İstatistikleri
+profile-anime-stats-body =
+    İzlenen gün: { $days }
+    Ortalama puan: { $mean }
+    İzliyor: { $watching }
+    Tamamladı: { $completed }
+    Beklemede: { $on_hold }
+    Bıraktı: { $dropped }
+    İzleyecek: { $planned }
+    Toplam kayıt: { $total }
+    Bölüm: { $episodes }
+profile-manga-stats = Manga İstatistikleri
+profile-manga-stats-body =
+    Okunan gün: { $days }
+    Ortalama puan: { $mean }
+    Okuyor: { $reading }
+    Tamamladı: { $completed }
+    Beklemede: { $on_hold }
+    Bıraktı: { $dropped }
+    Okuyacak: { $planned }
+    Toplam kayıt: { $total }
+    Bölüm: { $chapters }
+profile-favorite-anime = Favori Animeler
+profile-favorite-manga = Favori Mangalar
+profile-favorite-characters = Favori Karakterler
+profile-anime-updates = Son Anime Güncellemeleri
+profile-manga-updates = Son Manga Güncellemeleri
+profile-update-default = Güncellendi
+profile-update-scored = puan { $score }
+profile-invalid-username = '{ $user }' geçerli bir MyAnimeList kullanıcı adı değil.
+profile-not-found = '{ $user }' adlı MyAnimeList kullanıcısı bulunamadı. Profil olmayabilir ya da bir hata oluştu.
+user-not-found = '{ $user }' adlı MyAnimeList kullanıcısı bulunamadı.
+mal-not-linked = Henüz bir MyAnimeList hesabı bağlamadın. Önce `{ $prefix }mal link <username>` kullan ya da birine `{ $prefix }mal <username>` ile bak.
+mal-linked = Discord hesabın '{ $user }' adlı MyAnimeList kullanıcısına bağlandı. Profilini görmek için `{ $prefix }mal` yaz.
+mal-compare-not-linked = Kendinle karşılaştırmak için MyAnimeList hesabını `{ $prefix }mal link <username>` ile bağla ya da iki kullanıcı adı ver: `{ $prefix }mal compare <user1> <user2>`.
+mal-unlinked = MyAnimeList hesabının bağlantısı kaldırıldı.
+mal-no-link = Bağlı bir MyAnimeList hesabın yok.
+
+## Kullanıcı karşılaştırma
+
+compare-compatibility = Uyum: { $bar }
+compare-no-data = İki profilde de karşılaştırılacak herkese açık favori ya da istatistik yok.
+compare-user-stats = { $user } - Anime İstatistikleri
+compare-stats-body =
+    Ortalama puan: { $mean }
+    İzlenen gün: { $days }
+    Tamamladı: { $completed }
+    Bıraktı: { $dropped }
+compare-shared-anime = Ortak Favori Animeler
+compare-shared-manga = Ortak Favori Mangalar
+compare-shared-characters = Ortak Favori Karakterler
+compare-only = Sadece { $user }
+compare-same-user = Karşılaştırmak için iki farklı kullanıcı seç.
+
+## Anime karşılaştırma
+
+anime-compare-wins = **{ $title }** sıralamalı 4 ölçütün { $count } tanesini kazanıyor.
+anime-compare-tie = Sıralamalı ölçütlerde berabere.
+anime-compare-usage = Kullanım: `{ $prefix }anime compare <id|ad> <id|ad>`. Adları `vs` ile ayır, örneğin `{ $prefix }anime compare Naruto vs Bleach`.
+anime-compare-ambiguous = '{ $query }' birden fazla animeyle eşleşiyor. Bunun yerine bir MAL ID kullan:
+
+## Yardım
+
+help-title = Ferivonus Anime Bot Yardım
+help-intro = Merhaba! Ben bir anime botuyum. Komut kategorileri aşağıda; aralarında gezinmek için düğmeleri kullan.
+help-footer = Sayfa { $page }/{ $total } - Bir komutun ayrıntıları için { $prefix }help <komut> yaz
+help-category-title = Yardım: { $category }
+help-optional = (isteğe bağlı)
+help-arguments = Argümanlar
+help-examples = Örnekler
+help-aliases = Diğer Adlar
+help-cooldown = Bekleme Süresi
+help-cooldown-guild = Kullanıcı başına { $user } sn, sunucu başına { $guild } sn
+help-permission = Yetki
+help-admins-only = Yalnızca sunucu yöneticileri
+help-heavy-command = Sunucu bazı rollerle sınırlamadıkça herkes
+help-unknown-command = Bilinmeyen komut `{ $command }`. Tüm komutlar için `{ $prefix }help` yaz.
+
+category-anime = Arama ve Ayrıntılar
+category-media = Bölümler, Medya ve Topluluk
+category-discover = Keşfet
+category-myanimelist = MyAnimeList Profilleri
+category-server = Sunucu Yönetimi
+category-general = Genel
+
+## Sunucu ayarları ve dil
+
+config-title = Sunucu Ayarları
+config-footer = Bir ayarı { $prefix }config set <anahtar> <değer> ile değiştir
+config-updated = `{ $key }` ayarı { $value } olarak güncellendi.
+setting-error-prefix = Önek boşluk içermeyen 1 ila { $max } karakter olmalı.
+setting-error-language = Desteklenen diller: { $languages }.
+setting-error-nsfw = NSFW politikası `age_restricted`, `block` ya da `allow` olmalı.
+setting-error-channels = Kanalları #etiket ya da ID olarak yaz veya `all` kullan.
+setting-error-denied-channels = Kanalları #etiket ya da ID olarak yaz veya `none` kullan.
+setting-error-roles = Rolleri @etiket ya da ID olarak yaz veya `all` kullan.
+setting-error-denied-roles = Rolleri @etiket ya da ID olarak yaz veya `none` kullan.
+setting-error-color = Embed rengi `#5865F2` gibi bir hex renk ya da `default` olmalı.
+setting-error-cooldown = `<komut> <kullanıcı başına saniye> [sunucu başına saniye]` biçimini en fazla 3600 saniyeyle ya da `<komut> default` kullan.
+setting-error-unknown = Bilinmeyen ayar `{ $key }`. Ayarlar: { $keys }.
+perms-title = Komut İzinleri
+perms-description = Komutlar izin verilen kanallarda yanıtlanır, kanal ayrıca yasaklanmadıysa. Ağır komutları ({ $commands }) yalnızca izin verilen roller kullanabilir, üyede yasaklı bir rol de yoksa. Yöneticiler bunları her zaman kullanabilir.
+perms-footer = Bir listeyi { $prefix }perms allow|deny channels|roles <hedefler> ile değiştir
+perms-error-list = `{ $list }` bir liste değil. `channels` ya da `roles` kullan.
+language-name = Türkçe
+language-current = Bot seninle { $language } konuşuyor. `{ $prefix }language <{ $languages }>` ile değiştirebilir ya da `{ $prefix }language default` ile sunucunun diline dönebilirsin.
+language-set = Bundan sonra sana { $language } cevap vereceğim.
+language-reset = Bot artık seninle yine sunucunun dilinde konuşacak.
+language-invalid = Desteklenen diller: { $languages }; sunucunun dili için `default`.
+
+## Komut yardımları
+
+help-help = Komut kategorilerini listeler ya da tek bir komutu argümanları ve örnekleriyle açıklar.
+help-language = Botun sana hangi dilde cevap verdiğini gösterir ya da değiştirir. `default` sunucunun dilini kullanır.
+help-anime-details = Bir animenin ayrıntılı bilgilerini MyAnimeList ID'si ya da adıyla getirir. Birden fazla eşleşen adlarda seçim menüsü gösterilir.
+help-anime-recommendations = Verilen MyAnimeList ID'si ya da adına göre anime önerileri getirir.
+help-anime-related = Devam, öncül, yan hikâye, uyarlama ve yan yapımları listeler.
+help-anime-watchorder = Tüm seri için kronolojik bir izleme sırası hazırlar.
+help-anime-episodes = Bölümleri yayın tarihi, puan ve filler/özet işaretleriyle listeler.
+help-anime-episode = Tek bir bölümün özetini gösterir.
+help-anime-where = Yayın platformlarını ve resmi bağlantıları listeler.
+help-anime-stats = Liste istatistiklerini ve puan dağılımı grafiğini gösterir.
+help-anime-reviews = Kullanıcı incelemelerinde gezinir; spoilerlar gizlenir ve metnin tamamı DM olarak gönderilebilir.
+help-anime-news = Bir animeyle ilgili son haberleri gösterir.
+help-anime-pictures = Animenin resimlerini galeri olarak gösterir.
+help-anime-videos = Tanıtım ve müzik videolarını listeler.
+help-anime-themes = Açılış ve kapanış şarkılarını YouTube ve Spotify arama bağlantılarıyla listeler.
+help-anime-compare = İki animeyi yan yana karşılaştırır ve her sıralamalı ölçütün kazananını gösterir. Adları `vs` ile ayır.
+help-news-subscribe = (Yöneticiler) Bu animelerin yeni haberlerini bulunulan kanalda paylaşır.
+help-news-unsubscribe = (Yöneticiler) Bu animelerin ya da tümünün haberlerini bulunulan kanalda paylaşmayı bırakır.
+help-news-list = (Yöneticiler) Bu sunucunun haber aboneliklerini listeler.
+help-random = Rastgele bir kayıt çeker, istenirse filtrelere uyana kadar tekrar dener: `anime`/`manga`/`character`, `sfw`, `score=<min>`, `genre=<name>`, `type=<type>`.
+help-genres = Anime türlerini, temalarını ve hedef kitlelerini kayıt sayılarıyla listeler.
+help-genre = Bir türün en yüksek puanlı animelerini listeler. Yakın yazımlar da eşleşir.
+help-studio = Bir stüdyo ya da yapımcıyı animeleriyle birlikte puana göre sıralı gösterir.
+help-mal-compare = İki MyAnimeList kullanıcısının favorilerini ve istatistiklerini karşılaştırıp uyumlarını puanlar. Tek bir adla bağlı hesabınla karşılaştırır.
+help-mal-link = MyAnimeList hesabını bağlar, böylece `!mal` senin profilini gösterir.
+help-mal-unlink = MyAnimeList hesabının bağlantısını kaldırır.
+help-mal = Bir MyAnimeList profilini istatistikler, favoriler ve son güncellemelerle gösterir. Kullanıcı adı verilmezse bağlı hesabını gösterir.
+help-config-show = (Yöneticiler) Bu sunucunun bot ayarlarını gösterir.
+help-config-set = (Yöneticiler) Bir ayarı değiştirir: `prefix`, `language` (en, tr), `nsfw` (age_restricted, block, allow), `allowed_channels` (#kanallar ya da all), `embed_color` (#RRGGBB ya da default), `cooldowns` (<komut> <kullanıcı başına saniye> [sunucu başına saniye] ya da <komut> default).
+help-perms-show = (Yöneticiler) Botun hangi kanallarda yanıt verdiğini ve ağır komutları hangi rollerin kullanabildiğini gösterir.
+help-perms-allow = (Yöneticiler) Botun yanıt verdiği kanalları ya da ağır komutları kullanabilen rolleri belirler. `all` sınırı kaldırır.
+help-perms-deny = (Yöneticiler) Botun görmezden geldiği kanalları ya da ağır komutları kullanamayan rolleri belirler. `none` listeyi temizler.
+help-lain = Serial Experiments Lain hakkında ayrıntıları getirir.
+help-lain-recommendations = Serial Experiments Lain'e göre öneriler getirir.
+help-anime = Anime arar ve temel bilgileri listeler.
+
+arg-command = Açıklanacak komut, örneğin anime details
+arg-language = en, tr ya da sunucunun dili için default
+arg-mal-id = Animenin MyAnimeList ID'si
+arg-anime = Animenin MyAnimeList ID'si ya da adı
+arg-page = Bölüm listesinin sayfası
+arg-episode = Bölüm numarası
+arg-anime-pair = İki MAL ID ya da vs ile ayrılmış iki ad
+arg-news-ids = Takip edilecek MyAnimeList ID'leri, boşlukla ayrılmış
+arg-news-ids-or-all = Takibi bırakılacak MyAnimeList ID'leri ya da all
+arg-random-filters = anime, manga ya da character, ardından sfw, score=<min>, genre=<name>, type=<type>
+arg-genre = Tür, tema ya da hedef kitle adı
+arg-studio = Stüdyo ya da yapımcı adı
+arg-username = MyAnimeList kullanıcı adı
+arg-second-username = İkinci kullanıcı adı; verilmezse bağlı hesabın kullanılır
+arg-own-username = MyAnimeList kullanıcı adın
+arg-username-or-linked = MyAnimeList kullanıcı adı; verilmezse bağlı hesabın kullanılır
+arg-config-key = prefix, language, nsfw, allowed_channels, embed_color ya da cooldowns
+arg-config-value = Ayarın yeni değeri
+arg-perms-list = channels ya da roles
+arg-perms-targets = #kanallar ya da @roller, all veya none
+arg-search = Aranacak ad
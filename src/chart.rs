@@ -0,0 +1,191 @@
+// Pure Rust PNG rendering for the `!anime stats` score distribution chart. There is no font
+// dependency: the few characters the chart needs are drawn from the 3x5 bitmaps below.
+
+const WIDTH: u32 = 600;
+const HEIGHT: u32 = 300;
+const MARGIN_X: u32 = 20;
+const MARGIN_TOP: u32 = 30;
+const MARGIN_BOTTOM: u32 = 30;
+const BAR_WIDTH: u32 = 40;
+const GLYPH_SCALE: u32 = 3;
+
+const BACKGROUND: [u8; 3] = [0x2B, 0x2D, 0x31];
+const AXIS: [u8; 3] = [0x80, 0x84, 0x8E];
+const TEXT: [u8; 3] = [0xDB, 0xDE, 0xE1];
+const LOW_SCORE: [u8; 3] = [0xE7, 0x4C, 0x3C];
+const HIGH_SCORE: [u8; 3] = [0x2E, 0xCC, 0x71];
+
+// Rows of each glyph, top to bottom; the three low bits are the pixels, left to right.
+fn glyph(c: char) -> Option<[u8; 5]> {
+    let rows = match c {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b001, 0b001, 0b001],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        '%' => [0b101, 0b001, 0b010, 0b100, 0b101],
+        _ => return None,
+    };
+    Some(rows)
+}
+
+struct Canvas {
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new() -> Self {
+        Canvas {
+            pixels: BACKGROUND.repeat((WIDTH * HEIGHT) as usize),
+        }
+    }
+
+    fn fill_rect(&mut self, x: u32, y: u32, width: u32, height: u32, color: [u8; 3]) {
+        for py in y..(y + height).min(HEIGHT) {
+            for px in x..(x + width).min(WIDTH) {
+                let offset = ((py * WIDTH + px) * 3) as usize;
+                self.pixels[offset..offset + 3].copy_from_slice(&color);
+            }
+        }
+    }
+
+    fn text_width(text: &str) -> u32 {
+        let count = text.chars().count() as u32;
+        (count * 4).saturating_sub(1) * GLYPH_SCALE
+    }
+
+    // Draws `text` horizontally centered on `center_x`, with its top edge at `y`.
+    fn draw_text(&mut self, text: &str, center_x: u32, y: u32, color: [u8; 3]) {
+        let mut x = center_x.saturating_sub(Self::text_width(text) / 2);
+        for c in text.chars() {
+            if let Some(rows) = glyph(c) {
+                for (row, bits) in rows.iter().enumerate() {
+                    for column in 0..3 {
+                        if bits & (0b100 >> column) != 0 {
+                            self.fill_rect(
+                                x + column * GLYPH_SCALE,
+                                y + row as u32 * GLYPH_SCALE,
+                                GLYPH_SCALE,
+                                GLYPH_SCALE,
+                                color,
+                            );
+                        }
+                    }
+                }
+            }
+            x += 4 * GLYPH_SCALE;
+        }
+    }
+}
+
+fn blend(from: [u8; 3], to: [u8; 3], t: f32) -> [u8; 3] {
+    let mut color = [0; 3];
+    for i in 0..3 {
+        color[i] = (from[i] as f32 + (to[i] as f32 - from[i] as f32) * t).round() as u8;
+    }
+    color
+}
+
+// `votes[i]` is the number of votes for score `i + 1`.
+pub fn render_score_chart(votes: &[u32; 10]) -> Result<Vec<u8>, png::EncodingError> {
+    let mut canvas = Canvas::new();
+    let total: u64 = votes.iter().map(|&v| v as u64).sum();
+    let max_votes = votes.iter().copied().max().unwrap_or(0).max(1);
+
+    let slot_width = (WIDTH - 2 * MARGIN_X) / 10;
+    let baseline = HEIGHT - MARGIN_BOTTOM;
+    let max_bar_height = baseline - MARGIN_TOP - 5 * GLYPH_SCALE - 4;
+
+    canvas.fill_rect(MARGIN_X, baseline, WIDTH - 2 * MARGIN_X, 2, AXIS);
+
+    for (i, &count) in votes.iter().enumerate() {
+        let center_x = MARGIN_X + slot_width * i as u32 + slot_width / 2;
+        let bar_height = (count as u64 * max_bar_height as u64 / max_votes as u64) as u32;
+        let bar_top = baseline - bar_height;
+        let color = blend(LOW_SCORE, HIGH_SCORE, i as f32 / 9.0);
+
+        canvas.fill_rect(
+            center_x - BAR_WIDTH / 2,
+            bar_top,
+            BAR_WIDTH,
+            bar_height,
+            color,
+        );
+        canvas.draw_text(&(i + 1).to_string(), center_x, baseline + 8, TEXT);
+
+        if let Some(percent) = (count as u64 * 100 + total / 2).checked_div(total) {
+            let label_y = bar_top.saturating_sub(5 * GLYPH_SCALE + 4);
+            canvas.draw_text(&format!("{}%", percent), center_x, label_y, TEXT);
+        }
+    }
+
+    let mut png_bytes = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut png_bytes, WIDTH, HEIGHT);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&canvas.pixels)?;
+    }
+    Ok(png_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Decodes a rendered chart into its size and RGB pixels.
+    fn decode(png_bytes: &[u8]) -> (u32, u32, Vec<u8>) {
+        let mut reader = png::Decoder::new(png_bytes).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!(info.color_type, png::ColorType::Rgb);
+        pixels.truncate(info.buffer_size());
+        (info.width, info.height, pixels)
+    }
+
+    fn pixel(pixels: &[u8], x: u32, y: u32) -> [u8; 3] {
+        let start = ((y * WIDTH + x) * 3) as usize;
+        [pixels[start], pixels[start + 1], pixels[start + 2]]
+    }
+
+    // The pixel in the middle of the chart area above score `score`'s label.
+    fn bar_middle(pixels: &[u8], score: u32) -> [u8; 3] {
+        let slot_width = (WIDTH - 2 * MARGIN_X) / 10;
+        let center_x = MARGIN_X + slot_width * (score - 1) + slot_width / 2;
+        pixel(pixels, center_x, (MARGIN_TOP + HEIGHT - MARGIN_BOTTOM) / 2)
+    }
+
+    #[test]
+    fn renders_a_png_with_a_bar_per_score() {
+        let votes = [1, 0, 0, 0, 0, 0, 0, 5, 10, 4];
+        let (width, height, pixels) = decode(&render_score_chart(&votes).unwrap());
+        assert_eq!((width, height), (WIDTH, HEIGHT));
+        assert_eq!(pixels.len(), (WIDTH * HEIGHT * 3) as usize);
+
+        // The most voted score gets a full height bar, an empty score gets none.
+        assert_eq!(
+            bar_middle(&pixels, 9),
+            blend(LOW_SCORE, HIGH_SCORE, 8.0 / 9.0)
+        );
+        assert_eq!(bar_middle(&pixels, 2), BACKGROUND);
+    }
+
+    #[test]
+    fn renders_empty_and_extreme_votes() {
+        let (width, height, pixels) = decode(&render_score_chart(&[0; 10]).unwrap());
+        assert_eq!((width, height), (WIDTH, HEIGHT));
+        for score in 1..=10 {
+            assert_eq!(bar_middle(&pixels, score), BACKGROUND);
+        }
+
+        let (_, _, pixels) = decode(&render_score_chart(&[u32::MAX; 10]).unwrap());
+        assert_eq!(bar_middle(&pixels, 1), LOW_SCORE);
+        assert_eq!(bar_middle(&pixels, 10), HIGH_SCORE);
+    }
+}
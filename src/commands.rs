@@ -0,0 +1,1064 @@
+// The command registry. Every command the bot answers is declared once in `COMMANDS`; prefix
+// messages and slash commands are parsed against it, and `!help`, the slash command list and the
+// tests below are generated from it.
+
+use crate::i18n::{Locale, LOCALES};
+use fluent_bundle::FluentValue;
+use serenity::builder::{CreateCommand, CreateCommandOption};
+use serenity::model::application::CommandOptionType;
+use std::time::Duration;
+
+// Discord's limits for slash command and option descriptions.
+const SLASH_DESCRIPTION_LIMIT: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgKind {
+    // A positive MyAnimeList ID.
+    MalId,
+    // A positive number, like a page or an episode.
+    Number,
+    // A single word.
+    Word,
+    // Everything left in the message. Only allowed as the last argument.
+    Text,
+}
+
+#[derive(Debug)]
+pub struct ArgSpec {
+    pub name: &'static str,
+    pub kind: ArgKind,
+    pub required: bool,
+    // Catalog key of the description shown in `!help` and the slash command.
+    pub description: &'static str,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Permission {
+    Everyone,
+    // Server owner, or a role with Administrator or Manage Server.
+    GuildAdmin,
+    // Commands that make many Jikan requests. Everyone can use them unless the server limits them
+    // to some roles with `!perms`.
+    Heavy,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandId {
+    Help,
+    Language,
+    AnimeSearch,
+    AnimeDetails,
+    AnimeRecommendations,
+    AnimeRelated,
+    AnimeWatchOrder,
+    AnimeEpisodes,
+    AnimeEpisode,
+    AnimeWhere,
+    AnimeStats,
+    AnimeReviews,
+    AnimeNews,
+    AnimePictures,
+    AnimeVideos,
+    AnimeThemes,
+    AnimeCompare,
+    NewsSubscribe,
+    NewsUnsubscribe,
+    NewsList,
+    ConfigShow,
+    ConfigSet,
+    PermsShow,
+    PermsAllow,
+    PermsDeny,
+    Random,
+    Genres,
+    Genre,
+    Studio,
+    MalProfile,
+    MalCompare,
+    MalLink,
+    MalUnlink,
+    Lain,
+    LainRecommendations,
+}
+
+// Groups commands on the `!help` pages, in the order the pages are shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    Anime,
+    Media,
+    Discover,
+    MyAnimeList,
+    Server,
+    General,
+}
+
+pub const CATEGORIES: [Category; 6] = [
+    Category::Anime,
+    Category::Media,
+    Category::Discover,
+    Category::MyAnimeList,
+    Category::Server,
+    Category::General,
+];
+
+impl Category {
+    pub fn title_key(self) -> &'static str {
+        match self {
+            Category::Anime => "category-anime",
+            Category::Media => "category-media",
+            Category::Discover => "category-discover",
+            Category::MyAnimeList => "category-myanimelist",
+            Category::Server => "category-server",
+            Category::General => "category-general",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CommandSpec {
+    pub id: CommandId,
+    pub category: Category,
+    // The words after the prefix, e.g. `anime details`.
+    pub name: &'static str,
+    pub aliases: &'static [&'static str],
+    pub args: &'static [ArgSpec],
+    // Catalog key of the help text.
+    pub help: &'static str,
+    // Written without the prefix, which differs per server.
+    pub examples: &'static [&'static str],
+    // How long a user has to wait between two uses of the command. Servers can change both
+    // cooldowns with `!config set cooldowns`.
+    pub cooldown: Duration,
+    // How long anyone on the same server has to wait after someone used the command.
+    pub guild_cooldown: Duration,
+    pub permission: Permission,
+}
+
+const MAL_ID: ArgSpec = ArgSpec {
+    name: "MAL_ID",
+    kind: ArgKind::MalId,
+    required: true,
+    description: "arg-mal-id",
+};
+const ANIME: ArgSpec = ArgSpec {
+    name: "MAL_ID or name",
+    kind: ArgKind::Text,
+    required: true,
+    description: "arg-anime",
+};
+const PERMS_LIST: ArgSpec = ArgSpec {
+    name: "channels or roles",
+    kind: ArgKind::Word,
+    required: true,
+    description: "arg-perms-list",
+};
+const PERMS_TARGETS: ArgSpec = ArgSpec {
+    name: "targets",
+    kind: ArgKind::Text,
+    required: true,
+    description: "arg-perms-targets",
+};
+const NO_ARGS: &[ArgSpec] = &[];
+
+const fn seconds(secs: u64) -> Duration {
+    Duration::from_secs(secs)
+}
+
+pub static COMMANDS: &[CommandSpec] = &[
+    CommandSpec {
+        id: CommandId::Help,
+        category: Category::General,
+        name: "help",
+        aliases: &[],
+        args: &[ArgSpec {
+            name: "command",
+            kind: ArgKind::Text,
+            required: false,
+            description: "arg-command",
+        }],
+        help: "help-help",
+        examples: &["help", "help anime details"],
+        cooldown: seconds(0),
+        guild_cooldown: seconds(0),
+        permission: Permission::Everyone,
+    },
+    CommandSpec {
+        id: CommandId::Language,
+        category: Category::General,
+        name: "language",
+        aliases: &["lang"],
+        args: &[ArgSpec {
+            name: "language",
+            kind: ArgKind::Word,
+            required: false,
+            description: "arg-language",
+        }],
+        help: "help-language",
+        examples: &["language", "language tr", "language default"],
+        cooldown: seconds(0),
+        guild_cooldown: seconds(0),
+        permission: Permission::Everyone,
+    },
+    CommandSpec {
+        id: CommandId::AnimeDetails,
+        category: Category::Anime,
+        name: "anime details",
+        aliases: &["anime info"],
+        args: &[ANIME],
+        help: "help-anime-details",
+        examples: &["anime details 16498", "anime details Attack on Titan"],
+        cooldown: seconds(3),
+        guild_cooldown: seconds(0),
+        permission: Permission::Everyone,
+    },
+    CommandSpec {
+        id: CommandId::AnimeRecommendations,
+        category: Category::Anime,
+        name: "anime recommendations",
+        aliases: &["anime recs"],
+        args: &[ANIME],
+        help: "help-anime-recommendations",
+        examples: &["anime recommendations 16498"],
+        cooldown: seconds(3),
+        guild_cooldown: seconds(0),
+        permission: Permission::Everyone,
+    },
+    CommandSpec {
+        id: CommandId::AnimeRelated,
+        category: Category::Anime,
+        name: "anime related",
+        aliases: &[],
+        args: &[MAL_ID],
+        help: "help-anime-related",
+        examples: &["anime related 16498"],
+        cooldown: seconds(3),
+        guild_cooldown: seconds(0),
+        permission: Permission::Everyone,
+    },
+    CommandSpec {
+        id: CommandId::AnimeWatchOrder,
+        category: Category::Anime,
+        name: "anime watchorder",
+        aliases: &["anime order"],
+        args: &[MAL_ID],
+        help: "help-anime-watchorder",
+        examples: &["anime watchorder 16498"],
+        cooldown: seconds(30),
+        guild_cooldown: seconds(10),
+        permission: Permission::Heavy,
+    },
+    CommandSpec {
+        id: CommandId::AnimeEpisodes,
+        category: Category::Media,
+        name: "anime episodes",
+        aliases: &["anime eps"],
+        args: &[MAL_ID, ArgSpec { name: "page", kind: ArgKind::Number, required: false, description: "arg-page" }],
+        help: "help-anime-episodes",
+        examples: &["anime episodes 21", "anime episodes 21 3"],
+        cooldown: seconds(3),
+        guild_cooldown: seconds(0),
+        permission: Permission::Everyone,
+    },
+    CommandSpec {
+        id: CommandId::AnimeEpisode,
+        category: Category::Media,
+        name: "anime episode",
+        aliases: &["anime ep"],
+        args: &[MAL_ID, ArgSpec { name: "episode", kind: ArgKind::Number, required: true, description: "arg-episode" }],
+        help: "help-anime-episode",
+        examples: &["anime episode 21 1"],
+        cooldown: seconds(3),
+        guild_cooldown: seconds(0),
+        permission: Permission::Everyone,
+    },
+    CommandSpec {
+        id: CommandId::AnimeWhere,
+        category: Category::Anime,
+        name: "anime where",
+        aliases: &["anime watch"],
+        args: &[ANIME],
+        help: "help-anime-where",
+        examples: &["anime where Frieren"],
+        cooldown: seconds(3),
+        guild_cooldown: seconds(0),
+        permission: Permission::Everyone,
+    },
+    CommandSpec {
+        id: CommandId::AnimeStats,
+        category: Category::Media,
+        name: "anime stats",
+        aliases: &[],
+        args: &[MAL_ID],
+        help: "help-anime-stats",
+        examples: &["anime stats 16498"],
+        cooldown: seconds(5),
+        guild_cooldown: seconds(0),
+        permission: Permission::Everyone,
+    },
+    CommandSpec {
+        id: CommandId::AnimeReviews,
+        category: Category::Media,
+        name: "anime reviews",
+        aliases: &[],
+        args: &[MAL_ID],
+        help: "help-anime-reviews",
+        examples: &["anime reviews 16498"],
+        cooldown: seconds(3),
+        guild_cooldown: seconds(0),
+        permission: Permission::Everyone,
+    },
+    CommandSpec {
+        id: CommandId::AnimeNews,
+        category: Category::Media,
+        name: "anime news",
+        aliases: &[],
+        args: &[MAL_ID],
+        help: "help-anime-news",
+        examples: &["anime news 16498"],
+        cooldown: seconds(3),
+        guild_cooldown: seconds(0),
+        permission: Permission::Everyone,
+    },
+    CommandSpec {
+        id: CommandId::AnimePictures,
+        category: Category::Media,
+        name: "anime pictures",
+        aliases: &["anime pics"],
+        args: &[MAL_ID],
+        help: "help-anime-pictures",
+        examples: &["anime pictures 339"],
+        cooldown: seconds(3),
+        guild_cooldown: seconds(0),
+        permission: Permission::Everyone,
+    },
+    CommandSpec {
+        id: CommandId::AnimeVideos,
+        category: Category::Media,
+        name: "anime videos",
+        aliases: &[],
+        args: &[MAL_ID],
+        help: "help-anime-videos",
+        examples: &["anime videos 339"],
+        cooldown: seconds(3),
+        guild_cooldown: seconds(0),
+        permission: Permission::Everyone,
+    },
+    CommandSpec {
+        id: CommandId::AnimeThemes,
+        category: Category::Media,
+        name: "anime themes",
+        aliases: &["anime songs"],
+        args: &[MAL_ID],
+        help: "help-anime-themes",
+        examples: &["anime themes 1"],
+        cooldown: seconds(3),
+        guild_cooldown: seconds(0),
+        permission: Permission::Everyone,
+    },
+    CommandSpec {
+        id: CommandId::AnimeCompare,
+        category: Category::Anime,
+        name: "anime compare",
+        aliases: &["anime vs"],
+        args: &[ArgSpec { name: "anime vs anime", kind: ArgKind::Text, required: true, description: "arg-anime-pair" }],
+        help: "help-anime-compare",
+        examples: &["anime compare 5114 9253", "anime compare Naruto vs Bleach"],
+        cooldown: seconds(5),
+        guild_cooldown: seconds(0),
+        permission: Permission::Heavy,
+    },
+    CommandSpec {
+        id: CommandId::NewsSubscribe,
+        category: Category::Server,
+        name: "news subscribe",
+        aliases: &[],
+        args: &[ArgSpec { name: "MAL_IDs", kind: ArgKind::Text, required: true, description: "arg-news-ids" }],
+        help: "help-news-subscribe",
+        examples: &["news subscribe 16498 52991"],
+        cooldown: seconds(3),
+        guild_cooldown: seconds(0),
+        permission: Permission::GuildAdmin,
+    },
+    CommandSpec {
+        id: CommandId::NewsUnsubscribe,
+        category: Category::Server,
+        name: "news unsubscribe",
+        aliases: &[],
+        args: &[ArgSpec { name: "MAL_IDs or all", kind: ArgKind::Text, required: true, description: "arg-news-ids-or-all" }],
+        help: "help-news-unsubscribe",
+        examples: &["news unsubscribe 16498", "news unsubscribe all"],
+        cooldown: seconds(3),
+        guild_cooldown: seconds(0),
+        permission: Permission::GuildAdmin,
+    },
+    CommandSpec {
+        id: CommandId::NewsList,
+        category: Category::Server,
+        name: "news list",
+        aliases: &[],
+        args: NO_ARGS,
+        help: "help-news-list",
+        examples: &["news list"],
+        cooldown: seconds(3),
+        guild_cooldown: seconds(0),
+        permission: Permission::GuildAdmin,
+    },
+    CommandSpec {
+        id: CommandId::Random,
+        category: Category::Discover,
+        name: "random",
+        aliases: &["roll"],
+        args: &[ArgSpec { name: "filters", kind: ArgKind::Text, required: false, description: "arg-random-filters" }],
+        help: "help-random",
+        examples: &["random", "random anime sfw score=7 genre=Comedy"],
+        cooldown: seconds(5),
+        guild_cooldown: seconds(0),
+        permission: Permission::Everyone,
+    },
+    CommandSpec {
+        id: CommandId::Genres,
+        category: Category::Discover,
+        name: "genres",
+        aliases: &[],
+        args: NO_ARGS,
+        help: "help-genres",
+        examples: &["genres"],
+        cooldown: seconds(3),
+        guild_cooldown: seconds(0),
+        permission: Permission::Everyone,
+    },
+    CommandSpec {
+        id: CommandId::Genre,
+        category: Category::Discover,
+        name: "genre",
+        aliases: &[],
+        args: &[ArgSpec { name: "name", kind: ArgKind::Text, required: true, description: "arg-genre" }],
+        help: "help-genre",
+        examples: &["genre slice of life"],
+        cooldown: seconds(3),
+        guild_cooldown: seconds(0),
+        permission: Permission::Everyone,
+    },
+    CommandSpec {
+        id: CommandId::Studio,
+        category: Category::Discover,
+        name: "studio",
+        aliases: &["producer"],
+        args: &[ArgSpec { name: "name", kind: ArgKind::Text, required: true, description: "arg-studio" }],
+        help: "help-studio",
+        examples: &["studio Madhouse"],
+        cooldown: seconds(3),
+        guild_cooldown: seconds(0),
+        permission: Permission::Everyone,
+    },
+    CommandSpec {
+        id: CommandId::MalCompare,
+        category: Category::MyAnimeList,
+        name: "mal compare",
+        aliases: &[],
+        args: &[
+            ArgSpec { name: "user1", kind: ArgKind::Word, required: true, description: "arg-username" },
+            ArgSpec { name: "user2", kind: ArgKind::Word, required: false, description: "arg-second-username" },
+        ],
+        help: "help-mal-compare",
+        examples: &["mal compare Xinil Kineta"],
+        cooldown: seconds(5),
+        guild_cooldown: seconds(0),
+        permission: Permission::Heavy,
+    },
+    CommandSpec {
+        id: CommandId::MalLink,
+        category: Category::MyAnimeList,
+        name: "mal link",
+        aliases: &[],
+        args: &[ArgSpec { name: "username", kind: ArgKind::Word, required: true, description: "arg-own-username" }],
+        help: "help-mal-link",
+        examples: &["mal link Xinil"],
+        cooldown: seconds(3),
+        guild_cooldown: seconds(0),
+        permission: Permission::Everyone,
+    },
+    CommandSpec {
+        id: CommandId::MalUnlink,
+        category: Category::MyAnimeList,
+        name: "mal unlink",
+        aliases: &[],
+        args: NO_ARGS,
+        help: "help-mal-unlink",
+        examples: &["mal unlink"],
+        cooldown: seconds(0),
+        guild_cooldown: seconds(0),
+        permission: Permission::Everyone,
+    },
+    CommandSpec {
+        id: CommandId::MalProfile,
+        category: Category::MyAnimeList,
+        name: "mal",
+        aliases: &[],
+        args: &[ArgSpec { name: "username", kind: ArgKind::Word, required: false, description: "arg-username-or-linked" }],
+        help: "help-mal",
+        examples: &["mal Xinil", "mal"],
+        cooldown: seconds(3),
+        guild_cooldown: seconds(0),
+        permission: Permission::Everyone,
+    },
+    CommandSpec {
+        id: CommandId::ConfigShow,
+        category: Category::Server,
+        name: "config show",
+        aliases: &["config"],
+        args: NO_ARGS,
+        help: "help-config-show",
+        examples: &["config show"],
+        cooldown: seconds(0),
+        guild_cooldown: seconds(0),
+        permission: Permission::GuildAdmin,
+    },
+    CommandSpec {
+        id: CommandId::ConfigSet,
+        category: Category::Server,
+        name: "config set",
+        aliases: &[],
+        args: &[
+            ArgSpec {
+                name: "key",
+                kind: ArgKind::Word,
+                required: true,
+                description: "arg-config-key",
+            },
+            ArgSpec {
+                name: "value",
+                kind: ArgKind::Text,
+                required: true,
+                description: "arg-config-value",
+            },
+        ],
+        help: "help-config-set",
+        examples: &[
+            "config set prefix ?",
+            "config set embed_color #5865F2",
+            "config set cooldowns anime watchorder 60 20",
+        ],
+        cooldown: seconds(0),
+        guild_cooldown: seconds(0),
+        permission: Permission::GuildAdmin,
+    },
+    CommandSpec {
+        id: CommandId::PermsShow,
+        category: Category::Server,
+        name: "perms show",
+        aliases: &["perms"],
+        args: NO_ARGS,
+        help: "help-perms-show",
+        examples: &["perms"],
+        cooldown: seconds(0),
+        guild_cooldown: seconds(0),
+        permission: Permission::GuildAdmin,
+    },
+    CommandSpec {
+        id: CommandId::PermsAllow,
+        category: Category::Server,
+        name: "perms allow",
+        aliases: &[],
+        args: &[PERMS_LIST, PERMS_TARGETS],
+        help: "help-perms-allow",
+        examples: &["perms allow channels #anime #bot-commands", "perms allow roles all"],
+        cooldown: seconds(0),
+        guild_cooldown: seconds(0),
+        permission: Permission::GuildAdmin,
+    },
+    CommandSpec {
+        id: CommandId::PermsDeny,
+        category: Category::Server,
+        name: "perms deny",
+        aliases: &[],
+        args: &[PERMS_LIST, PERMS_TARGETS],
+        help: "help-perms-deny",
+        examples: &["perms deny roles @Muted", "perms deny channels none"],
+        cooldown: seconds(0),
+        guild_cooldown: seconds(0),
+        permission: Permission::GuildAdmin,
+    },
+    CommandSpec {
+        id: CommandId::Lain,
+        category: Category::Discover,
+        name: "lain",
+        aliases: &[],
+        args: NO_ARGS,
+        help: "help-lain",
+        examples: &["lain"],
+        cooldown: seconds(3),
+        guild_cooldown: seconds(0),
+        permission: Permission::Everyone,
+    },
+    CommandSpec {
+        id: CommandId::LainRecommendations,
+        category: Category::Discover,
+        name: "lain recommendations",
+        aliases: &[],
+        args: NO_ARGS,
+        help: "help-lain-recommendations",
+        examples: &["lain recommendations"],
+        cooldown: seconds(3),
+        guild_cooldown: seconds(0),
+        permission: Permission::Everyone,
+    },
+    // Last, since every other `!anime ...` command is a longer match.
+    CommandSpec {
+        id: CommandId::AnimeSearch,
+        category: Category::Anime,
+        name: "anime",
+        aliases: &["search"],
+        args: &[ArgSpec { name: "anime name", kind: ArgKind::Text, required: true, description: "arg-search" }],
+        help: "help-anime",
+        examples: &["anime Attack on Titan"],
+        cooldown: seconds(3),
+        guild_cooldown: seconds(0),
+        permission: Permission::Everyone,
+    },
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArgValue {
+    Number(u32),
+    Text(String),
+}
+
+#[derive(Debug)]
+pub struct Invocation {
+    pub spec: &'static CommandSpec,
+    // One entry per `spec.args`, `None` for optional arguments that were left out.
+    values: Vec<Option<ArgValue>>,
+}
+
+impl Invocation {
+    fn value(&self, name: &str) -> Option<&ArgValue> {
+        let index = self.spec.args.iter().position(|arg| arg.name == name)?;
+        self.values.get(index)?.as_ref()
+    }
+
+    pub fn number(&self, name: &str) -> Option<u32> {
+        match self.value(name)? {
+            ArgValue::Number(number) => Some(*number),
+            ArgValue::Text(_) => None,
+        }
+    }
+
+    pub fn text(&self, name: &str) -> Option<&str> {
+        match self.value(name)? {
+            ArgValue::Text(text) => Some(text),
+            ArgValue::Number(_) => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct UsageError {
+    pub spec: &'static CommandSpec,
+    // Catalog key of what went wrong, and the values for its placeholders.
+    pub problem: &'static str,
+    pub args: Vec<(&'static str, String)>,
+}
+
+impl UsageError {
+    pub fn message(&self, prefix: &str, locale: Locale) -> String {
+        let args: Vec<(&str, FluentValue)> = self
+            .args
+            .iter()
+            .map(|(name, value)| (*name, value.as_str().into()))
+            .collect();
+        locale.t_args(
+            "usage-line",
+            &[
+                ("problem", locale.t_args(self.problem, &args).into()),
+                ("usage", usage(self.spec, prefix).into()),
+            ],
+        )
+    }
+}
+
+// `!anime episodes <MAL_ID> [page]`
+pub fn usage(spec: &CommandSpec, prefix: &str) -> String {
+    let mut usage = format!("{}{}", prefix, spec.name);
+    for arg in spec.args {
+        if arg.required {
+            usage.push_str(&format!(" <{}>", arg.name));
+        } else {
+            usage.push_str(&format!(" [{}]", arg.name));
+        }
+    }
+    usage
+}
+
+// How many leading words of `words` the command name (or an alias) takes, if it matches.
+fn matched_words(spec: &CommandSpec, words: &[&str]) -> Option<usize> {
+    std::iter::once(spec.name)
+        .chain(spec.aliases.iter().copied())
+        .filter_map(|name| {
+            let name_words: Vec<&str> = name.split(' ').collect();
+            let matches = name_words.len() <= words.len()
+                && name_words
+                    .iter()
+                    .zip(words)
+                    .all(|(expected, word)| expected.eq_ignore_ascii_case(word));
+            matches.then_some(name_words.len())
+        })
+        .max()
+}
+
+fn parse_args(spec: &'static CommandSpec, words: &[&str]) -> Result<Invocation, UsageError> {
+    let usage_error = |problem: &'static str, args: Vec<(&'static str, String)>| UsageError {
+        spec,
+        problem,
+        args,
+    };
+    let mut values = Vec::with_capacity(spec.args.len());
+    let mut remaining = words;
+
+    for arg in spec.args {
+        let value = match (arg.kind, remaining) {
+            (_, []) if arg.required => {
+                return Err(usage_error(
+                    "usage-missing",
+                    vec![("arg", arg.name.to_string())],
+                ))
+            }
+            (_, []) => None,
+            (ArgKind::Text, rest) => {
+                remaining = &[];
+                Some(ArgValue::Text(rest.join(" ")))
+            }
+            (ArgKind::Word, [word, rest @ ..]) => {
+                remaining = rest;
+                Some(ArgValue::Text(word.to_string()))
+            }
+            (ArgKind::MalId | ArgKind::Number, [word, rest @ ..]) => {
+                remaining = rest;
+                match word.parse::<u32>() {
+                    Ok(number) if number >= 1 => Some(ArgValue::Number(number)),
+                    _ if arg.kind == ArgKind::MalId => {
+                        return Err(usage_error("usage-invalid-mal-id", Vec::new()))
+                    }
+                    _ => {
+                        return Err(usage_error(
+                            "usage-invalid-number",
+                            vec![("arg", arg.name.to_string())],
+                        ))
+                    }
+                }
+            }
+        };
+        values.push(value);
+    }
+
+    if !remaining.is_empty() {
+        return Err(usage_error(
+            "usage-unexpected",
+            vec![("text", remaining.join(" "))],
+        ));
+    }
+    Ok(Invocation { spec, values })
+}
+
+// Parses a message with its prefix already removed. `None` when it names no known command.
+pub fn parse(content: &str) -> Option<Result<Invocation, UsageError>> {
+    let words: Vec<&str> = content.split_whitespace().collect();
+    let (spec, taken) = COMMANDS
+        .iter()
+        .filter_map(|spec| matched_words(spec, &words).map(|taken| (spec, taken)))
+        .max_by_key(|(_, taken)| *taken)?;
+    Some(parse_args(spec, &words[taken..]))
+}
+
+// Slash commands can't contain spaces, so `anime details` becomes `/anime-details`.
+pub fn slash_name(spec: &CommandSpec) -> String {
+    spec.name.replace(' ', "-")
+}
+
+fn slash_option_name(arg: &ArgSpec) -> String {
+    let name: String = arg
+        .name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    name.trim_matches('_').to_string()
+}
+
+fn slash_description(key: &str, locale: Locale) -> String {
+    let plain = locale.t(key).replace('`', "");
+    match plain.char_indices().nth(SLASH_DESCRIPTION_LIMIT - 3) {
+        Some((cut, _)) => format!("{}...", &plain[..cut]),
+        None => plain,
+    }
+}
+
+pub fn slash_commands() -> Vec<CreateCommand> {
+    COMMANDS
+        .iter()
+        .map(|spec| {
+            let options = spec.args.iter().map(|arg| {
+                let kind = match arg.kind {
+                    ArgKind::MalId | ArgKind::Number => CommandOptionType::Integer,
+                    ArgKind::Word | ArgKind::Text => CommandOptionType::String,
+                };
+                let mut option = CreateCommandOption::new(
+                    kind,
+                    slash_option_name(arg),
+                    slash_description(arg.description, Locale::En),
+                )
+                .required(arg.required);
+                for locale in &LOCALES[1..] {
+                    option = option.description_localized(
+                        locale.code(),
+                        slash_description(arg.description, *locale),
+                    );
+                }
+                if kind == CommandOptionType::Integer {
+                    option.min_int_value(1)
+                } else {
+                    option
+                }
+            });
+            // English is the default; Discord shows the other languages to users whose client
+            // is set to them.
+            let mut command = CreateCommand::new(slash_name(spec))
+                .description(slash_description(spec.help, Locale::En))
+                .set_options(options.collect());
+            for locale in &LOCALES[1..] {
+                command = command
+                    .description_localized(locale.code(), slash_description(spec.help, *locale));
+            }
+            command
+        })
+        .collect()
+}
+
+// Rebuilds the text form of a slash command (without prefix) so both go through `parse`. `option`
+// looks up a slash option's value by name.
+pub fn slash_to_text(name: &str, option: impl Fn(&str) -> Option<String>) -> Option<String> {
+    let spec = COMMANDS.iter().find(|spec| slash_name(spec) == name)?;
+    let mut text = spec.name.to_string();
+    for arg in spec.args {
+        if let Some(value) = option(&slash_option_name(arg)) {
+            text.push(' ');
+            text.push_str(&value);
+        }
+    }
+    Some(text)
+}
+
+// Looks a command up by its name or an alias, as typed after `!help`.
+pub fn find_command(name: &str) -> Option<&'static CommandSpec> {
+    // Whatever the server's prefix is, `!help !anime details` should work too.
+    let name = name
+        .trim()
+        .trim_start_matches(|c: char| c.is_ascii_punctuation());
+    let words: Vec<&str> = name.split_whitespace().collect();
+    COMMANDS
+        .iter()
+        .find(|spec| matched_words(spec, &words) == Some(words.len()))
+}
+
+// The registry entry of a command.
+pub fn spec(id: CommandId) -> &'static CommandSpec {
+    COMMANDS
+        .iter()
+        .find(|spec| spec.id == id)
+        .expect("every command is in the registry")
+}
+
+pub fn in_category(category: Category) -> impl Iterator<Item = &'static CommandSpec> {
+    COMMANDS
+        .iter()
+        .filter(move |spec| spec.category == category)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parsed(content: &str) -> Invocation {
+        parse(content)
+            .expect("known command")
+            .expect("valid arguments")
+    }
+
+    #[test]
+    fn every_example_parses_to_its_command() {
+        for spec in COMMANDS {
+            for example in spec.examples {
+                let invocation = parsed(example);
+                assert_eq!(invocation.spec.id, spec.id, "{}", example);
+            }
+        }
+    }
+
+    #[test]
+    fn names_and_aliases_are_unique() {
+        let mut seen = std::collections::HashSet::new();
+        for spec in COMMANDS {
+            for name in std::iter::once(&spec.name).chain(spec.aliases) {
+                assert!(
+                    seen.insert(name.to_lowercase()),
+                    "duplicate command name {}",
+                    name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn text_arguments_come_last() {
+        for spec in COMMANDS {
+            if let Some(position) = spec.args.iter().position(|arg| arg.kind == ArgKind::Text) {
+                assert_eq!(position, spec.args.len() - 1, "{}", spec.name);
+            }
+            let first_optional = spec
+                .args
+                .iter()
+                .position(|arg| !arg.required)
+                .unwrap_or(spec.args.len());
+            assert!(
+                spec.args[first_optional..].iter().all(|arg| !arg.required),
+                "{}",
+                spec.name
+            );
+        }
+    }
+
+    #[test]
+    fn slash_commands_follow_discord_rules() {
+        let valid = |name: &str| {
+            !name.is_empty()
+                && name.len() <= 32
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+        };
+        assert!(COMMANDS.len() <= 100);
+        for spec in COMMANDS {
+            assert!(valid(&slash_name(spec)), "{}", spec.name);
+            for locale in LOCALES {
+                let description = slash_description(spec.help, locale);
+                assert!(!description.is_empty() && description.chars().count() <= SLASH_DESCRIPTION_LIMIT);
+            }
+            for arg in spec.args {
+                assert!(valid(&slash_option_name(arg)), "{} {}", spec.name, arg.name);
+                for locale in LOCALES {
+                    let description = slash_description(arg.description, locale);
+                    assert!(!description.is_empty() && description.chars().count() <= SLASH_DESCRIPTION_LIMIT);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn slash_commands_round_trip_through_the_parser() {
+        for spec in COMMANDS {
+            let Some(example) = spec.examples.first() else {
+                continue;
+            };
+            let invocation = parsed(example);
+            let text = slash_to_text(&slash_name(spec), |option| {
+                let arg = spec
+                    .args
+                    .iter()
+                    .find(|arg| slash_option_name(arg) == option)?;
+                invocation.value(arg.name).map(|value| match value {
+                    ArgValue::Number(number) => number.to_string(),
+                    ArgValue::Text(text) => text.clone(),
+                })
+            })
+            .expect("slash name is registered");
+            assert_eq!(parsed(&text).spec.id, spec.id, "{}", text);
+        }
+    }
+
+    #[test]
+    fn every_command_has_a_help_page() {
+        for spec in COMMANDS {
+            assert!(CATEGORIES.contains(&spec.category), "{}", spec.name);
+            assert!(in_category(spec.category).any(|listed| listed.id == spec.id));
+            assert!(!spec.examples.is_empty(), "{} has no examples", spec.name);
+            for name in std::iter::once(&spec.name).chain(spec.aliases) {
+                assert_eq!(
+                    find_command(name).map(|found| found.id),
+                    Some(spec.id),
+                    "{}",
+                    name
+                );
+            }
+        }
+        assert_eq!(
+            find_command("!Anime Details").map(|spec| spec.id),
+            Some(CommandId::AnimeDetails)
+        );
+        assert!(find_command("anime details 339").is_none());
+    }
+
+    #[test]
+    fn usage_uses_the_given_prefix() {
+        let spec = parsed("anime episodes 21").spec;
+        assert_eq!(usage(spec, "?"), "?anime episodes <MAL_ID> [page]");
+    }
+
+    #[test]
+    fn longest_name_wins() {
+        assert_eq!(parsed("anime details 339").spec.id, CommandId::AnimeDetails);
+        assert_eq!(
+            parsed("anime detailed look").spec.id,
+            CommandId::AnimeSearch
+        );
+        assert_eq!(
+            parsed("lain recommendations").spec.id,
+            CommandId::LainRecommendations
+        );
+        assert_eq!(
+            parsed("ANIME Episodes 21").spec.id,
+            CommandId::AnimeEpisodes
+        );
+        assert_eq!(parsed("mal compare a b").spec.id, CommandId::MalCompare);
+    }
+
+    #[test]
+    fn arguments_are_typed() {
+        let invocation = parsed("anime episodes 21 3");
+        assert_eq!(invocation.number("MAL_ID"), Some(21));
+        assert_eq!(invocation.number("page"), Some(3));
+        assert_eq!(parsed("anime episodes 21").number("page"), None);
+        assert_eq!(
+            parsed("anime   Cowboy  Bebop").text("anime name"),
+            Some("Cowboy Bebop")
+        );
+    }
+
+    #[test]
+    fn bad_arguments_give_usage_errors() {
+        let error = parse("anime stats abc").unwrap().unwrap_err();
+        assert_eq!(
+            error.message("?", Locale::En),
+            "Invalid MAL ID. Please provide a numeric ID. Usage: `?anime stats <MAL_ID>`"
+        );
+        assert_eq!(
+            error.message("?", Locale::Tr),
+            "Geçersiz MAL ID. Lütfen sayısal bir ID girin. Kullanım: `?anime stats <MAL_ID>`"
+        );
+        assert!(parse("anime episode 21").unwrap().is_err());
+        assert!(parse("anime episodes 21 0").unwrap().is_err());
+        assert!(parse("genres extra").unwrap().is_err());
+        assert!(parse("anime").unwrap().is_err());
+    }
+
+    #[test]
+    fn unknown_commands_are_ignored() {
+        assert!(parse("hello").is_none());
+        assert!(parse("unknown").is_none());
+        assert!(parse("").is_none());
+    }
+}
@@ -1,12 +1,27 @@
 use serenity::async_trait;
-use serenity::model::channel::Message;
+use serenity::model::channel::{Channel, Message};
 use serenity::model::gateway::{Presence, Ready};
 use serenity::prelude::*;
-use serenity::builder::{CreateEmbed, CreateEmbedFooter, CreateMessage};
+use serenity::builder::{CreateActionRow, CreateAttachment, CreateButton, CreateEmbed, CreateEmbedAuthor, CreateEmbedFooter, CreateInteractionResponse, CreateInteractionResponseFollowup, CreateInteractionResponseMessage, CreateMessage, CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption, EditInteractionResponse, EditMessage};
+use serenity::model::application::{ButtonStyle, Command, CommandDataOptionValue, CommandInteraction, ComponentInteraction, ComponentInteractionDataKind, Interaction};
+use serenity::model::id::{ChannelId, GuildId, MessageId, RoleId, UserId};
 use serenity::model::Timestamp;
 
-use crate::jikan_api;
+use crate::chart;
+use crate::commands::{self, CommandId, CommandSpec, Invocation, Permission};
+use crate::embed;
+use crate::guild_settings::{self, GuildSettings, NsfwPolicy};
+use crate::i18n::{Locale, LOCALES};
+use crate::news_feed;
+use crate::rate_limit::{self, Limited, Scope};
+use crate::taste::{self, Overlap, TasteProfile};
+use crate::user_settings;
+use crate::jikan_api::{self, AnimeDetails, CharacterDetails, ExternalLink, Genre, MalUrl, MangaDetails, Producer, ThemeSong, Title, UserProfile};
 use std::collections::HashMap;
+use std::env;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
 use tokio::fs;
 use serde::{Serialize, Deserialize};
 
@@ -50,301 +65,3081 @@ async fn save_queries(user_queries: &UserQueries) {
     }
 }
 
-async fn send_embed_message(ctx: &Context, msg: &Message, embed: CreateEmbed) {
-    let builder = CreateMessage::new().embed(embed);
-    if let Err(why) = msg.channel_id.send_message(&ctx.http, builder).await {
-        println!("Error sending message: {:?}", why);
+// Discord user IDs mapped to the MyAnimeList usernames they linked with `!mal link`.
+const MAL_LINKS_FILE: &str = "data/mal_links.json";
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct MalLinks {
+    links: HashMap<String, String>,
+}
+
+async fn load_mal_links() -> MalLinks {
+    match fs::read_to_string(MAL_LINKS_FILE).await {
+        Ok(data) => serde_json::from_str(&data).unwrap_or_default(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => MalLinks::default(),
+        Err(e) => {
+            eprintln!("Error loading MAL links file: {}", e);
+            MalLinks::default()
+        }
     }
 }
 
-async fn handle_anime_details_command(ctx: &Context, msg: &Message, mal_id: u32) {
-    if let Err(why) = msg.channel_id.say(&ctx.http, format!("Fetching details for MAL ID: {}...", mal_id)).await {
-        println!("Error sending message: {:?}", why);
+async fn save_mal_links(mal_links: &MalLinks) {
+    let json_data = serde_json::to_string_pretty(mal_links).expect("Failed to serialize MAL links");
+    if let Err(dir_err) = fs::create_dir_all("data").await {
+        eprintln!("Error creating 'data' directory before saving: {}", dir_err);
+        return;
     }
+    if let Err(e) = fs::write(MAL_LINKS_FILE, json_data).await {
+        eprintln!("Error saving MAL links to file: {}", e);
+    }
+}
 
-    match jikan_api::get_anime_details_by_id(mal_id).await {
-        Some(details) => {
-            let mut embed = CreateEmbed::new()
-                .title(format!("{} (MAL ID: {})", details.title, details.mal_id))
-                .url(&details.url)
-                .timestamp(Timestamp::now());
+// Who used a command, button or menu, where, and where the answers go. Prefix and slash commands
+// run the same handlers with it, and buttons and menus go through the same access checks.
+struct Caller {
+    user_id: UserId,
+    guild_id: Option<GuildId>,
+    channel_id: ChannelId,
+    // The caller's roles, when Discord sent them along with the event.
+    roles: Option<Vec<RoleId>>,
+    // `None` for buttons and menus, which answer through their own interaction.
+    reply_to: Option<ReplyTarget>,
+}
 
-            if let Some(synopsis) = &details.synopsis {
-                let short_synopsis = if synopsis.len() > 1000 {
-                    format!("{}...", &synopsis[..1000])
-                } else {
-                    synopsis.clone()
-                };
-                embed = embed.description(short_synopsis);
-            }
+enum ReplyTarget {
+    // The command message, which replies quote.
+    Message(MessageId),
+    // A deferred slash command. The first answer fills in its response, later ones follow it up.
+    Interaction { command: Box<CommandInteraction>, answered: AtomicBool },
+}
 
-            if let Some(images_res) = &details.images {
-                if let Some(jpg_images) = &images_res.jpg {
-                    if let Some(image_url) = &jpg_images.image_url {
-                        embed = embed.image(image_url);
-                    }
-                }
-            }
+// One message sent back to the caller.
+#[derive(Default)]
+struct Reply {
+    content: Option<String>,
+    embed: Option<CreateEmbed>,
+    components: Vec<CreateActionRow>,
+    attachment: Option<CreateAttachment>,
+    // Whether to quote the command message, if there is one.
+    quote: bool,
+}
 
-            if let Some(title_english) = &details.title_english {
-                embed = embed.field("English Title", title_english, true);
-            }
-            if let Some(title_japanese) = &details.title_japanese {
-                embed = embed.field("Japanese Title", title_japanese, true);
-            }
-            if !details.title_synonyms.is_empty() {
-                embed = embed.field("Synonyms", details.title_synonyms.join(", "), false);
-            }
+impl Caller {
+    fn of_message(msg: &Message) -> Self {
+        Caller {
+            user_id: msg.author.id,
+            guild_id: msg.guild_id,
+            channel_id: msg.channel_id,
+            roles: msg.member.as_ref().map(|member| member.roles.clone()),
+            reply_to: Some(ReplyTarget::Message(msg.id)),
+        }
+    }
 
-            embed = embed.field("Approved", details.approved.map_or("N/A".to_string(), |b| b.to_string()), true);
-            embed = embed.field("Type", details.anime_type.as_deref().unwrap_or("N/A"), true);
-            embed = embed.field("Source", details.source.as_deref().unwrap_or("N/A"), true);
-            embed = embed.field("Episodes", details.episodes.map_or("N/A".to_string(), |e| e.to_string()), true);
-            embed = embed.field("Status", &details.status, true);
-            embed = embed.field("Airing", details.airing.map_or("N/A".to_string(), |b| b.to_string()), true);
-            
-            if let Some(aired_string) = &details.aired.string {
-                embed = embed.field("Aired", aired_string, true);
-            } else {
-                let mut aired_dates = String::new();
-                if let Some(from) = &details.aired.from {
-                    aired_dates.push_str(&format!("From: {}", from));
+    fn of_command(command: &CommandInteraction) -> Self {
+        Caller {
+            user_id: command.user.id,
+            guild_id: command.guild_id,
+            channel_id: command.channel_id,
+            roles: command.member.as_ref().map(|member| member.roles.clone()),
+            reply_to: Some(ReplyTarget::Interaction { command: Box::new(command.clone()), answered: AtomicBool::new(false) }),
+        }
+    }
+
+    fn of_component(component: &ComponentInteraction) -> Self {
+        Caller {
+            user_id: component.user.id,
+            guild_id: component.guild_id,
+            channel_id: component.channel_id,
+            roles: component.member.as_ref().map(|member| member.roles.clone()),
+            reply_to: None,
+        }
+    }
+
+    fn is_slash_command(&self) -> bool {
+        matches!(self.reply_to, Some(ReplyTarget::Interaction { .. }))
+    }
+
+    async fn send(&self, ctx: &Context, reply: Reply) -> serenity::Result<Message> {
+        if let Some(ReplyTarget::Interaction { command, answered }) = &self.reply_to {
+            if !answered.swap(true, Ordering::SeqCst) {
+                let mut edit = EditInteractionResponse::new().components(reply.components);
+                if let Some(content) = reply.content {
+                    edit = edit.content(content);
                 }
-                if let Some(to) = &details.aired.to {
-                    if !aired_dates.is_empty() { aired_dates.push_str(", "); }
-                    aired_dates.push_str(&format!("To: {}", to));
+                if let Some(embed) = reply.embed {
+                    edit = edit.embed(embed);
                 }
-                if !aired_dates.is_empty() {
-                    embed = embed.field("Aired", aired_dates, true);
+                if let Some(attachment) = reply.attachment {
+                    edit = edit.new_attachment(attachment);
                 }
+                return command.edit_response(&ctx.http, edit).await;
             }
-
-            if let Some(duration) = &details.duration {
-                embed = embed.field("Duration", duration, true);
-            }
-            if let Some(rating) = &details.rating {
-                embed = embed.field("Rating", rating, true);
+            let mut followup = CreateInteractionResponseFollowup::new().components(reply.components);
+            if let Some(content) = reply.content {
+                followup = followup.content(content);
             }
-            if let Some(score) = details.score {
-                embed = embed.field("Score", format!("{}/10", score), true);
+            if let Some(embed) = reply.embed {
+                followup = followup.embed(embed);
             }
-            if let Some(scored_by) = details.scored_by {
-                embed = embed.field("Scored by", format!("{} users", scored_by), true);
+            if let Some(attachment) = reply.attachment {
+                followup = followup.add_file(attachment);
             }
-            if let Some(rank) = details.rank {
-                embed = embed.field("Rank", format!("#{}", rank), true);
+            return command.create_followup(&ctx.http, followup).await;
+        }
+
+        let mut builder = CreateMessage::new().components(reply.components);
+        if let Some(content) = reply.content {
+            builder = builder.content(content);
+        }
+        if let Some(embed) = reply.embed {
+            builder = builder.embed(embed);
+        }
+        if let Some(attachment) = reply.attachment {
+            builder = builder.add_file(attachment);
+        }
+        if let Some(ReplyTarget::Message(message_id)) = self.reply_to.as_ref().filter(|_| reply.quote) {
+            builder = builder.reference_message((self.channel_id, *message_id));
+        }
+        self.channel_id.send_message(&ctx.http, builder).await
+    }
+
+    async fn say(&self, ctx: &Context, content: impl Into<String>) -> serenity::Result<Message> {
+        self.send(ctx, Reply { content: Some(content.into()), ..Reply::default() }).await
+    }
+
+    // Like `say`, but quotes the command message.
+    async fn reply(&self, ctx: &Context, content: impl Into<String>) -> serenity::Result<Message> {
+        self.send(ctx, Reply { content: Some(content.into()), quote: true, ..Reply::default() }).await
+    }
+
+    // A slash command whose handler sent nothing would show "thinking" until Discord gives up.
+    async fn finish(&self, ctx: &Context) {
+        if let Some(ReplyTarget::Interaction { command, answered }) = &self.reply_to {
+            if !answered.load(Ordering::SeqCst) {
+                if let Err(why) = command.delete_response(&ctx.http).await {
+                    println!("Error deleting slash command response: {:?}", why);
+                }
             }
-            if let Some(popularity) = details.popularity {
-                embed = embed.field("Popularity", format!("#{}", popularity), true);
+        }
+    }
+}
+
+async fn send_embed_message(ctx: &Context, caller: &Caller, embed: CreateEmbed) {
+    send_embed_with_components(ctx, caller, embed, Vec::new()).await;
+}
+
+// Sends the rest of an embed that didn't fit in one message.
+async fn send_follow_ups(ctx: &Context, caller: &Caller, embeds: impl Iterator<Item = CreateEmbed>) {
+    for embed in embeds {
+        if let Err(why) = caller.send(ctx, Reply { embed: Some(embed), ..Reply::default() }).await {
+            println!("Error sending message: {:?}", why);
+            return;
+        }
+    }
+}
+
+// Posts `placeholder` while `reply` is built, then edits it into the embed. On an error the
+// placeholder is deleted and the error sent in its place.
+async fn send_with_placeholder(ctx: &Context, caller: &Caller, placeholder: String, reply: impl Future<Output = Result<CreateEmbed, String>>) {
+    // A deferred slash command already shows that the bot is working on it.
+    if caller.is_slash_command() {
+        match reply.await {
+            Ok(embed) => send_embed_message(ctx, caller, embed).await,
+            Err(error) => {
+                if let Err(why) = caller.say(ctx, error).await {
+                    println!("Error sending message: {:?}", why);
+                }
+            },
+        }
+        return;
+    }
+
+    let mut placeholder = match caller.say(ctx, placeholder).await {
+        Ok(placeholder) => Some(placeholder),
+        Err(why) => {
+            println!("Error sending message: {:?}", why);
+            None
+        }
+    };
+
+    match (reply.await, placeholder.as_mut()) {
+        (Ok(embed), Some(placeholder)) => {
+            let mut embeds = embed::split(guild_settings::get(caller.guild_id).await.style(embed)).into_iter();
+            if let Some(first) = embeds.next() {
+                if let Err(why) = placeholder.edit(&ctx.http, EditMessage::new().content("").embed(first)).await {
+                    println!("Error editing message: {:?}", why);
+                    return;
+                }
             }
-            if let Some(members) = details.members {
-                embed = embed.field("Members", members.to_string(), true);
+            send_follow_ups(ctx, caller, embeds).await;
+        },
+        (Ok(embed), None) => send_embed_message(ctx, caller, embed).await,
+        (Err(error), placeholder) => {
+            if let Some(placeholder) = placeholder {
+                if let Err(why) = placeholder.delete(&ctx.http).await {
+                    println!("Error deleting message: {:?}", why);
+                }
             }
-            if let Some(favorites) = details.favorites {
-                embed = embed.field("Favorites", favorites.to_string(), true);
+            if let Err(why) = caller.say(ctx, error).await {
+                println!("Error sending message: {:?}", why);
             }
+        },
+    }
+}
 
-            if let Some(trailer_meta) = &details.trailer {
-                if let Some(youtube_id) = &trailer_meta.youtube_id {
-                    embed = embed.field("Trailer", format!("<https://www.youtube.com/watch?v={}>", youtube_id), false);
-                }
-            }
+// Embeds too big for one message continue in follow-up messages. The components go on the first
+// one, since that's the message they edit.
+async fn send_embed_with_components(ctx: &Context, caller: &Caller, embed: CreateEmbed, components: Vec<CreateActionRow>) {
+    let mut embeds = embed::split(guild_settings::get(caller.guild_id).await.style(embed)).into_iter();
+    let Some(first) = embeds.next() else {
+        return;
+    };
+    if let Err(why) = caller.send(ctx, Reply { embed: Some(first), components, ..Reply::default() }).await {
+        println!("Error sending message: {:?}", why);
+        return;
+    }
+    send_follow_ups(ctx, caller, embeds).await;
+}
 
-            if !details.titles.is_empty() {
-                let alternative_titles: Vec<String> = details.titles.iter()
-                    .filter_map(|t| Some(t.title.clone()))
-                    .take(3)
-                    .collect();
-                if !alternative_titles.is_empty() {
-                    embed = embed.field("Alternative Titles", alternative_titles.join(", "), false);
-                }
+// Index of the first entry on the 1-based `page`, if every entry of the page has a `u32` index.
+// Page numbers also come from button custom IDs, which clients can forge.
+fn first_index(page: u32, page_size: u32) -> Option<u32> {
+    let first = page.checked_sub(1)?.checked_mul(page_size)?;
+    first.checked_add(page_size).map(|_| first)
+}
+
+// Previous/Next buttons whose custom IDs are `base_id` followed by the target page number.
+fn page_buttons(base_id: &str, page: u32, has_next: bool, locale: Locale) -> Vec<CreateActionRow> {
+    if page <= 1 && !has_next {
+        return Vec::new();
+    }
+    vec![CreateActionRow::Buttons(vec![
+        CreateButton::new(format!("{}{}", base_id, page.saturating_sub(1)))
+            .label(locale.t("page-previous"))
+            .style(ButtonStyle::Secondary)
+            .disabled(page <= 1),
+        CreateButton::new(format!("{}{}", base_id, page + 1))
+            .label(locale.t("page-next"))
+            .style(ButtonStyle::Secondary)
+            .disabled(!has_next),
+    ])]
+}
+
+// Joins lines until the next one would push the text past `max_len`, then notes how many were left out.
+fn join_lines_limited(lines: &[String], max_len: usize, locale: Locale) -> String {
+    let mut text = String::new();
+    for (i, line) in lines.iter().enumerate() {
+        let remaining = lines.len() - i;
+        let suffix = locale.t_args("and-more", &[("count", remaining.into())]);
+        if text.len() + line.len() + suffix.len() + 2 > max_len {
+            text.push_str(&suffix);
+            break;
+        }
+        text.push_str(line);
+        text.push('\n');
+    }
+    text
+}
+
+// Outcome of turning a `<id|name>` argument into a single anime.
+enum AnimeLookup {
+    Found(u32),
+    Ambiguous(Vec<jikan_api::Anime>),
+    NotFound,
+}
+
+// How a search result's names matched the query, most confident first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum TitleMatch {
+    Title,
+    English,
+    // A synonym or a title in another language.
+    Other,
+}
+
+fn title_match(query: &str, anime: &jikan_api::Anime) -> Option<TitleMatch> {
+    let query = normalize_name(query);
+    if normalize_name(&anime.title) == query {
+        return Some(TitleMatch::Title);
+    }
+    anime.titles.iter()
+        .filter(|title| normalize_name(&title.title) == query)
+        .map(|title| match title.title_type.as_str() {
+            "Default" => TitleMatch::Title,
+            "English" => TitleMatch::English,
+            _ => TitleMatch::Other,
+        })
+        .min()
+}
+
+// Numeric input is taken as a MAL ID. A name resolves to the single result matching it at the most
+// confident level (main title, then English title, then any other title), or to the only search result.
+// Several equally good matches, or none, leave the choice to the user.
+async fn resolve_anime(query: &str, sfw: bool) -> AnimeLookup {
+    if let Ok(mal_id) = query.parse::<u32>() {
+        return AnimeLookup::Found(mal_id);
+    }
+
+    let results = jikan_api::search_anime(query, sfw).await.unwrap_or_default();
+    if let [only] = results.as_slice() {
+        return AnimeLookup::Found(only.mal_id);
+    }
+    if results.is_empty() {
+        return AnimeLookup::NotFound;
+    }
+
+    let best = results.iter().filter_map(|anime| title_match(query, anime)).min();
+    if let Some(best) = best {
+        let mut matches = results.iter().filter(|anime| title_match(query, anime) == Some(best));
+        if let (Some(anime), None) = (matches.next(), matches.next()) {
+            return AnimeLookup::Found(anime.mal_id);
+        }
+    }
+    AnimeLookup::Ambiguous(results)
+}
+
+// Select menu custom IDs are this prefix followed by `<user ID>:<command>`: who may pick, and the
+// command the choice is for.
+const PICK_MENU_PREFIX: &str = "pick:";
+
+// Lets the user choose between ambiguous search results; the chosen MAL ID is the option value.
+fn disambiguation_menu(user_id: UserId, command: &str, candidates: &[jikan_api::Anime], locale: Locale) -> Vec<CreateActionRow> {
+    let options = candidates.iter().take(25).map(|anime| {
+        let mut details = Vec::new();
+        if let Some(anime_type) = &anime.anime_type {
+            details.push(anime_type.clone());
+        }
+        if let Some(year) = anime.year {
+            details.push(year.to_string());
+        }
+        if let Some(english) = anime.title_english.as_ref().filter(|english| **english != anime.title) {
+            details.push(english.clone());
+        }
+        let mut option = CreateSelectMenuOption::new(embed::truncate(&anime.title, 95), anime.mal_id.to_string());
+        if !details.is_empty() {
+            option = option.description(embed::truncate(&details.join(" - "), 95));
+        }
+        option
+    }).collect();
+
+    let menu = CreateSelectMenu::new(format!("{}{}:{}", PICK_MENU_PREFIX, user_id, command), CreateSelectMenuKind::String { options })
+        .placeholder(locale.t("anime-pick-placeholder"));
+    vec![CreateActionRow::SelectMenu(menu)]
+}
+
+// Resolves the `<id|name>` argument of `!anime details`/`!anime recommendations`. When the name is
+// ambiguous, or nothing matches, it replies itself and returns `None`.
+async fn resolve_anime_argument(ctx: &Context, caller: &Caller, query: &str, command: &str, sfw: bool, locale: Locale) -> Option<u32> {
+    match resolve_anime(query, sfw).await {
+        AnimeLookup::Found(mal_id) => Some(mal_id),
+        AnimeLookup::NotFound => {
+            if let Err(why) = caller.reply(ctx, locale.t_args("anime-not-found", &[("query", query.into())])).await {
+                println!("Error sending message: {:?}", why);
             }
+            None
+        },
+        AnimeLookup::Ambiguous(candidates) => {
+            let reply = Reply {
+                content: Some(locale.t_args("anime-ambiguous", &[("query", query.into())])),
+                components: disambiguation_menu(caller.user_id, command, &candidates, locale),
+                quote: true,
+                ..Reply::default()
+            };
+            if let Err(why) = caller.send(ctx, reply).await {
+                println!("Error sending message: {:?}", why);
+            }
+            None
+        }
+    }
+}
+
+// Refuses adult entries when `sfw` is set, i.e. where the server's content policy hides them.
+fn adult_refusal(details: &AnimeDetails, sfw: bool, locale: Locale) -> Result<(), String> {
+    if sfw && details.is_adult() {
+        return Err(locale.t_args("adult-blocked", &[("title", details.title.as_str().into())]));
+    }
+    Ok(())
+}
 
-            send_embed_message(ctx, msg, embed).await;
+async fn anime_details_reply(mal_id: u32, sfw: bool, locale: Locale) -> Result<CreateEmbed, String> {
+    match jikan_api::get_anime_details_by_id(mal_id).await {
+        Some(details) => {
+            adult_refusal(&details, sfw, locale)?;
+            let watch_links = fetch_watch_links(mal_id).await;
+            Ok(anime_details_embed(&details, Some(&watch_links), locale))
         },
-        None => {
-            if let Err(why) = msg.channel_id.say(&ctx.http, format!("Could not find details for MAL ID: {}. It might not exist or an error occurred.", mal_id)).await {
+        None => Err(locale.t_args("details-not-found", &[("id", mal_id.into())])),
+    }
+}
+
+async fn handle_anime_details_command(ctx: &Context, caller: &Caller, query: &str, sfw: bool, locale: Locale) {
+    let Some(mal_id) = resolve_anime_argument(ctx, caller, query, "details", sfw, locale).await else {
+        return;
+    };
+
+    match anime_details_reply(mal_id, sfw, locale).await {
+        Ok(embed) => send_embed_message(ctx, caller, embed).await,
+        Err(error) => {
+            if let Err(why) = caller.say(ctx, error).await {
                 println!("Error sending message: {:?}", why);
             }
         }
     }
 }
 
-async fn handle_anime_recommendations_command(ctx: &Context, msg: &Message, mal_id: u32) {
-    if let Err(why) = msg.channel_id.say(&ctx.http, format!("Fetching recommendations for MAL ID: {}...", mal_id)).await {
-        println!("Error sending message: {:?}", why);
+fn join_names(entries: &[MalUrl]) -> String {
+    entries.iter().map(|e| e.name.as_str()).collect::<Vec<&str>>().join(", ")
+}
+
+// How many streaming platforms and official links are listed before the rest are cut off.
+const MAX_WATCH_LINKS: usize = 10;
+
+struct WatchLinks {
+    streaming: Vec<ExternalLink>,
+    external: Vec<ExternalLink>,
+}
+
+// Jikan's streaming data is global, so every list of platforms carries the `streaming-region-note`
+// message. Override it with `STREAMING_REGION_NOTE` in `.env`.
+fn region_note(locale: Locale) -> String {
+    env::var("STREAMING_REGION_NOTE").unwrap_or_else(|_| locale.t("streaming-region-note"))
+}
+
+// A failed lookup just leaves its list empty; missing links shouldn't block the rest of the view.
+async fn fetch_watch_links(mal_id: u32) -> WatchLinks {
+    WatchLinks {
+        streaming: jikan_api::get_anime_streaming(mal_id).await.unwrap_or_default(),
+        external: jikan_api::get_anime_external_links(mal_id).await.unwrap_or_default(),
+    }
+}
+
+fn format_links(links: &[ExternalLink], locale: Locale) -> String {
+    let mut formatted: Vec<String> = links.iter().take(MAX_WATCH_LINKS).map(|l| format!("[{}]({})", l.name, l.url)).collect();
+    if links.len() > MAX_WATCH_LINKS {
+        formatted.push(locale.t_args("links-and-more", &[("count", (links.len() - MAX_WATCH_LINKS).into())]));
+    }
+    formatted.join(", ")
+}
+
+// Languages in the order the details embed lists them. Synonyms come last, after any language
+// missing here.
+const TITLE_LANGUAGES: [&str; 5] = ["English", "Japanese", "German", "Spanish", "French"];
+
+fn title_type_name(title_type: &str, locale: Locale) -> String {
+    match title_type {
+        "English" => locale.t("title-type-english"),
+        "Japanese" => locale.t("title-type-japanese"),
+        "German" => locale.t("title-type-german"),
+        "Spanish" => locale.t("title-type-spanish"),
+        "French" => locale.t("title-type-french"),
+        "Synonym" => locale.t("title-type-synonym"),
+        other => other.to_string(),
+    }
+}
+
+// One line per title type, e.g. "**German:** Angriff auf Titan". The default title is already the
+// embed's title, so it's left out.
+fn alternative_titles(titles: &[Title], locale: Locale) -> Vec<String> {
+    let mut groups: Vec<(&str, Vec<&str>)> = Vec::new();
+    for title in titles.iter().filter(|title| title.title_type != "Default") {
+        match groups.iter_mut().find(|(title_type, _)| *title_type == title.title_type) {
+            Some((_, names)) => names.push(&title.title),
+            None => groups.push((&title.title_type, vec![&title.title])),
+        }
+    }
+    groups.sort_by_key(|(title_type, _)| match TITLE_LANGUAGES.iter().position(|language| language == title_type) {
+        Some(position) => position,
+        None if *title_type == "Synonym" => TITLE_LANGUAGES.len() + 1,
+        None => TITLE_LANGUAGES.len(),
+    });
+    groups.iter()
+        .map(|(title_type, names)| format!("**{}:** {}", title_type_name(title_type, locale), names.join(", ")))
+        .collect()
+}
+
+// `watch_links` is only passed where the extra Jikan requests are worth it (e.g. not for every `!random` re-roll).
+fn anime_details_embed(details: &AnimeDetails, watch_links: Option<&WatchLinks>, locale: Locale) -> CreateEmbed {
+    let mut embed = CreateEmbed::new()
+        .title(format!("{} (MAL ID: {})", details.title, details.mal_id))
+        .url(&details.url)
+        .timestamp(Timestamp::now());
+
+    let mut description = String::new();
+    if let Some(synopsis) = &details.synopsis {
+        description.push_str(&embed::truncate(synopsis, 1000));
+    }
+    // Streaming and official links live in the description; the field list is already close to Discord's limit of 25.
+    if let Some(links) = watch_links {
+        if !links.streaming.is_empty() {
+            description.push_str(&format!("\n\n**{}:** {}", locale.t("field-streaming"), format_links(&links.streaming, locale)));
+            embed = embed.footer(CreateEmbedFooter::new(region_note(locale)));
+        }
+        if !links.external.is_empty() {
+            description.push_str(&format!("\n\n**{}:** {}", locale.t("field-official-links"), format_links(&links.external, locale)));
+        }
+    }
+    if !description.is_empty() {
+        embed = embed.description(description);
+    }
+
+    if let Some(image_url) = details.images.as_ref().and_then(|i| i.largest_url()) {
+        embed = embed.image(image_url);
+    }
+
+    let alternative_titles = alternative_titles(&details.titles, locale);
+    if !alternative_titles.is_empty() {
+        embed = embed.field(locale.t("field-alternative-titles"), join_lines_limited(&alternative_titles, 1024, locale), false);
+    }
+
+    let yes_no = |flag: bool| if flag { locale.t("yes") } else { locale.t("no") };
+    embed = embed.field(locale.t("field-approved"), details.approved.map_or(locale.t("not-available"), yes_no), true);
+    embed = embed.field(locale.t("field-type"), details.anime_type.clone().unwrap_or_else(|| locale.t("not-available")), true);
+    embed = embed.field(locale.t("field-source"), details.source.clone().unwrap_or_else(|| locale.t("not-available")), true);
+    embed = embed.field(locale.t("field-episodes"), details.episodes.map_or(locale.t("not-available"), |e| e.to_string()), true);
+    embed = embed.field(locale.t("field-status"), &details.status, true);
+    embed = embed.field(locale.t("field-airing"), details.airing.map_or(locale.t("not-available"), yes_no), true);
+    
+    if let Some(aired_string) = &details.aired.string {
+        embed = embed.field(locale.t("field-aired"), aired_string, true);
+    } else {
+        let mut aired_dates = String::new();
+        if let Some(from) = &details.aired.from {
+            aired_dates.push_str(&locale.t_args("details-aired-from", &[("date", from.as_str().into())]));
+        }
+        if let Some(to) = &details.aired.to {
+            if !aired_dates.is_empty() { aired_dates.push_str(", "); }
+            aired_dates.push_str(&locale.t_args("details-aired-to", &[("date", to.as_str().into())]));
+        }
+        if !aired_dates.is_empty() {
+            embed = embed.field(locale.t("field-aired"), aired_dates, true);
+        }
+    }
+
+    if let Some(duration) = &details.duration {
+        embed = embed.field(locale.t("field-duration"), duration, true);
+    }
+    if let Some(rating) = &details.rating {
+        embed = embed.field(locale.t("field-rating"), rating, true);
+    }
+    if let Some(score) = details.score {
+        let score_text = match details.scored_by {
+            Some(scored_by) => locale.t_args("details-score-by", &[("score", score.to_string().into()), ("users", scored_by.into())]),
+            None => format!("{}/10", score),
+        };
+        embed = embed.field(locale.t("field-score"), score_text, true);
+    }
+    if let Some(rank) = details.rank {
+        embed = embed.field(locale.t("field-rank"), format!("#{}", rank), true);
+    }
+    if let Some(popularity) = details.popularity {
+        embed = embed.field(locale.t("field-popularity"), format!("#{}", popularity), true);
+    }
+    if let Some(members) = details.members {
+        embed = embed.field(locale.t("field-members"), members.to_string(), true);
+    }
+    if let Some(favorites) = details.favorites {
+        embed = embed.field(locale.t("field-favorites"), favorites.to_string(), true);
+    }
+
+    // Explicit genres share the field with regular genres to stay under Discord's 25 field limit.
+    let all_genres: Vec<&MalUrl> = details.genres.iter().chain(&details.explicit_genres).collect();
+    if !all_genres.is_empty() {
+        let names: Vec<&str> = all_genres.iter().map(|g| g.name.as_str()).collect();
+        embed = embed.field(locale.t("field-genres"), names.join(", "), false);
     }
+    if !details.themes.is_empty() {
+        embed = embed.field(locale.t("field-themes"), join_names(&details.themes), false);
+    }
+    if !details.demographics.is_empty() {
+        embed = embed.field(locale.t("field-demographic"), join_names(&details.demographics), true);
+    }
+    if !details.studios.is_empty() {
+        embed = embed.field(locale.t("field-studios"), join_names(&details.studios), true);
+    }
+    if !details.producers.is_empty() {
+        embed = embed.field(locale.t("field-producers"), join_names(&details.producers), false);
+    }
+    if !details.licensors.is_empty() {
+        embed = embed.field(locale.t("field-licensors"), join_names(&details.licensors), true);
+    }
+
+    if let Some(trailer_meta) = &details.trailer {
+        if let Some(trailer_url) = trailer_meta.watch_url() {
+            embed = embed.field(locale.t("field-trailer"), format!("<{}>", trailer_url), false);
+        }
+        if let Some(thumbnail_url) = trailer_meta.thumbnail_url() {
+            embed = embed.thumbnail(thumbnail_url);
+        }
+    }
+
+    embed
+}
 
+async fn anime_recommendations_reply(mal_id: u32, sfw: bool, locale: Locale) -> Result<CreateEmbed, String> {
+    if sfw {
+        if let Some(details) = jikan_api::get_anime_details_by_id(mal_id).await {
+            adult_refusal(&details, sfw, locale)?;
+        }
+    }
     match jikan_api::get_anime_recommendations(mal_id).await {
         Some(recommendations) => {
             if recommendations.is_empty() {
-                if let Err(why) = msg.channel_id.say(&ctx.http, format!("No recommendations found for MAL ID: {}.", mal_id)).await {
-                    println!("Error sending message: {:?}", why);
-                }
-            } else {
-                let mut embed = CreateEmbed::new()
-                    .title(format!("Recommendations for MAL ID: {}", mal_id))
-                    .timestamp(Timestamp::now());
+                return Err(locale.t_args("recommendations-none", &[("id", mal_id.into())]));
+            }
+            let mut embed = CreateEmbed::new()
+                .title(locale.t_args("recommendations-title", &[("id", mal_id.into())]))
+                .timestamp(Timestamp::now());
 
-                for (i, rec_item) in recommendations.iter().take(5).enumerate() {
-                    let mut field_value = String::new();
-                    if let Some(jpg_images) = &rec_item.entry.images.jpg {
-                        if let Some(image_url) = &jpg_images.image_url {
-                            field_value.push_str(&format!("{}\n", image_url));
-                        }
+            for (i, rec_item) in recommendations.iter().take(5).enumerate() {
+                let mut field_value = String::new();
+                if let Some(jpg_images) = &rec_item.entry.images.jpg {
+                    if let Some(image_url) = &jpg_images.image_url {
+                        field_value.push_str(&format!("{}\n", image_url));
                     }
-                    field_value.push_str(&format!("{}", rec_item.entry.url));
-
-                    embed = embed.field(
-                        format!("{}. {} (MAL ID: {})", i + 1, rec_item.entry.title, rec_item.entry.mal_id),
-                        field_value,
-                        false,
-                    );
                 }
+                field_value.push_str(&rec_item.entry.url);
 
-                send_embed_message(ctx, msg, embed).await;
+                embed = embed.field(
+                    format!("{}. {} (MAL ID: {})", i + 1, rec_item.entry.title, rec_item.entry.mal_id),
+                    field_value,
+                    false,
+                );
             }
+            Ok(embed)
         },
-        None => {
-            if let Err(why) = msg.channel_id.say(&ctx.http, format!("An error occurred while fetching recommendations for MAL ID: {}. Please try again later.", mal_id)).await {
+        None => Err(locale.t_args("recommendations-error", &[("id", mal_id.into())])),
+    }
+}
+
+async fn handle_anime_recommendations_command(ctx: &Context, caller: &Caller, query: &str, sfw: bool, locale: Locale) {
+    let Some(mal_id) = resolve_anime_argument(ctx, caller, query, "recommendations", sfw, locale).await else {
+        return;
+    };
+
+    match anime_recommendations_reply(mal_id, sfw, locale).await {
+        Ok(embed) => send_embed_message(ctx, caller, embed).await,
+        Err(error) => {
+            if let Err(why) = caller.say(ctx, error).await {
                 println!("Error sending message: {:?}", why);
             }
         }
     }
 }
 
-async fn handle_anime_search_command(ctx: &Context, msg: &Message, query: &str, user_id: String) {
-    if let Err(why) = msg.channel_id.say(&ctx.http, format!("Searching for anime: '{}'...", query)).await {
-        println!("Error sending message: {:?}", why);
+// Answers a choice from the disambiguation menu by replacing the menu with the requested embed.
+async fn handle_pick_menu(ctx: &Context, component: &ComponentInteraction, command: &str) {
+    let ComponentInteractionDataKind::StringSelect { values } = &component.data.kind else {
+        return;
+    };
+    let Some(mal_id) = values.first().and_then(|value| value.parse::<u32>().ok()) else {
+        return;
+    };
+
+    if let Err(why) = component.defer(&ctx.http).await {
+        println!("Error acknowledging selection: {:?}", why);
+        return;
+    }
+
+    let settings = guild_settings::get(component.guild_id).await;
+    let locale = locale_for(component.user.id, &settings).await;
+    let sfw = !allows_adult_content(ctx, component.channel_id, &settings).await;
+    let reply = match command {
+        "details" => anime_details_reply(mal_id, sfw, locale).await,
+        "recommendations" => anime_recommendations_reply(mal_id, sfw, locale).await,
+        _ => return,
+    };
+    let edit = match reply {
+        Ok(embed) => EditInteractionResponse::new().content("").embed(embed::fit(settings.style(embed))),
+        Err(error) => EditInteractionResponse::new().content(error),
+    };
+    if let Err(why) = component.edit_response(&ctx.http, edit.components(Vec::new())).await {
+        println!("Error editing selection message: {:?}", why);
     }
+}
 
+async fn handle_anime_search_command(ctx: &Context, caller: &Caller, query: &str, user_id: String, prefix: &str, sfw: bool, locale: Locale) {
     let mut user_queries = load_queries().await;
     user_queries.queries.entry(user_id.clone()).or_insert_with(Vec::new).push(query.to_string());
     save_queries(&user_queries).await;
 
-    match jikan_api::search_anime(query).await {
+    match jikan_api::search_anime(query, sfw).await {
         Some(animes) => {
             if animes.is_empty() {
-                if let Err(why) = msg.channel_id.say(&ctx.http, format!("No results found for '{}'.", query)).await {
+                if let Err(why) = caller.say(ctx, locale.t_args("search-no-results", &[("query", query.into())])).await {
                     println!("Error sending message: {:?}", why);
                 }
             } else {
                 let mut embed = CreateEmbed::new()
-                    .title(format!("Search results for '{}'", query))
+                    .title(locale.t_args("search-title", &[("query", query.into())]))
                     .timestamp(Timestamp::now());
                 
                 let mut description = String::new();
                 for anime in animes {
-                    description.push_str(&format!("- MAL ID: {}, Title: {}\n", anime.mal_id, anime.title));
+                    description.push_str(&locale.t_args("search-line", &[("id", anime.mal_id.into()), ("title", anime.title.into())]));
+                    description.push('\n');
                 }
                 embed = embed.description(description);
-                embed = embed.footer(CreateEmbedFooter::new("To get more details, type `!anime details <MAL_ID>`"));
+                embed = embed.footer(CreateEmbedFooter::new(locale.t_args("search-footer", &[("prefix", prefix.into())])));
 
-                send_embed_message(ctx, msg, embed).await;
+                send_embed_message(ctx, caller, embed).await;
             }
         },
         None => {
-            if let Err(why) = msg.channel_id.say(&ctx.http, "An error occurred while searching for anime. Please try again later.").await {
+            if let Err(why) = caller.say(ctx, locale.t("search-error")).await {
                 println!("Error sending message: {:?}", why);
             }
         }
     }
 }
 
-#[async_trait]
-impl EventHandler for Handler {
-    async fn message(&self, ctx: Context, msg: Message) {
-        if msg.author.bot {
-            return;
+// How many times `!random` may re-roll while looking for an entry that matches the filters.
+const MAX_RANDOM_ROLLS: usize = 10;
+const RANDOM_BUTTON_PREFIX: &str = "random:";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RandomKind {
+    Anime,
+    Manga,
+    Character,
+}
+
+#[derive(Debug)]
+struct RandomOptions {
+    kind: RandomKind,
+    sfw: bool,
+    min_score: Option<f32>,
+    genre: Option<String>,
+    entry_type: Option<String>,
+}
+
+// Parses the arguments of `!random [anime|manga|character] [sfw] [score=<n>] [genre=<name>] [type=<type>]`.
+fn parse_random_options(args: &str, locale: Locale) -> Result<RandomOptions, String> {
+    let mut options = RandomOptions {
+        kind: RandomKind::Anime,
+        sfw: false,
+        min_score: None,
+        genre: None,
+        entry_type: None,
+    };
+
+    for token in args.split_whitespace() {
+        let lower = token.to_lowercase();
+        match lower.as_str() {
+            "anime" => options.kind = RandomKind::Anime,
+            "manga" => options.kind = RandomKind::Manga,
+            "character" | "characters" => options.kind = RandomKind::Character,
+            "sfw" => options.sfw = true,
+            _ => match lower.split_once('=') {
+                // `NaN` and `inf` parse as floats but aren't scores anyone can filter by.
+                Some(("score", value)) | Some(("min_score", value)) => match value.parse::<f32>() {
+                    Ok(score) if score.is_finite() => options.min_score = Some(score),
+                    _ => return Err(locale.t_args("random-invalid-score", &[("value", value.into())])),
+                },
+                Some(("genre", value)) if !value.is_empty() => options.genre = Some(value.replace('_', " ")),
+                Some(("type", value)) if !value.is_empty() => options.entry_type = Some(value.to_string()),
+                _ => return Err(locale.t_args("random-unknown-option", &[("option", token.into())])),
+            },
         }
+    }
 
-        let content = msg.content.trim();
-        let user_id = msg.author.id.to_string();
-
-        if content == "!help" {
-            let help_message = "Hello! I'm an anime bot. Here are the commands you can use:\n\n\
-                                • `!anime <anime name>`: Searches for anime and lists basic information.\n\n\
-                                • `!anime details <MAL_ID>`: Fetches detailed information for a specific anime by its MyAnimeList ID.\n\n\
-                                • `!anime recommendations <MAL_ID>`: Fetches anime recommendations based on a given MyAnimeList ID.\n\n\
-                                • `!lain`: Get details about Serial Experiments Lain.\n\n\
-                                • `!lain recommendations`: Get recommendations based on Serial Experiments Lain.\n\n\
-                                ----------------------------------------\n\n\
-                                **Example Usage:**\n\
-                                `!anime Attack on Titan`\n\
-                                `!anime details 16498` (for Attack on Titan)\n\
-                                `!anime recommendations 16498` (for Attack on Titan recommendations)\n\
-                                `!lain`\n\
-                                `!lain recommendations`";
-            
-            let embed = CreateEmbed::new()
-                .title("Ferivonus Anime Bot Yardım Menüsü")
-                .description(help_message)
-                .timestamp(Timestamp::now());
+    Ok(options)
+}
 
-            let builder = CreateMessage::new().embed(embed);
-            if let Err(why) = msg.channel_id.send_message(&ctx.http, builder).await {
-                println!("Error sending help message: {:?}", why);
-            }
-            return;
+fn has_genre(genres: &[MalUrl], wanted: &str) -> bool {
+    genres.iter().any(|g| g.name.eq_ignore_ascii_case(wanted))
+}
+
+fn anime_matches(details: &AnimeDetails, options: &RandomOptions) -> bool {
+    if options.sfw && details.is_adult() {
+        return false;
+    }
+    if let Some(min_score) = options.min_score {
+        if details.score.is_none_or(|s| s < min_score) {
+            return false;
+        }
+    }
+    if let Some(genre) = &options.genre {
+        if !has_genre(&details.genres, genre) && !has_genre(&details.themes, genre) && !has_genre(&details.demographics, genre) {
+            return false;
         }
+    }
+    if let Some(entry_type) = &options.entry_type {
+        if !details.anime_type.as_deref().is_some_and(|t| t.eq_ignore_ascii_case(entry_type)) {
+            return false;
+        }
+    }
+    true
+}
 
-        if content.starts_with("!anime details ") {
-            let parts: Vec<&str> = content.splitn(3, ' ').collect();
-            if parts.len() < 3 {
-                if let Err(why) = msg.reply(&ctx.http, "Please provide an anime ID for details. Usage: `!anime details <MAL_ID>`").await {
+fn manga_matches(details: &MangaDetails, options: &RandomOptions) -> bool {
+    if options.sfw && details.is_adult() {
+        return false;
+    }
+    if let Some(min_score) = options.min_score {
+        if details.score.is_none_or(|s| s < min_score) {
+            return false;
+        }
+    }
+    if let Some(genre) = &options.genre {
+        if !has_genre(&details.genres, genre) {
+            return false;
+        }
+    }
+    if let Some(entry_type) = &options.entry_type {
+        if !details.manga_type.as_deref().is_some_and(|t| t.eq_ignore_ascii_case(entry_type)) {
+            return false;
+        }
+    }
+    true
+}
+
+fn manga_details_embed(details: &MangaDetails, locale: Locale) -> CreateEmbed {
+    let mut embed = CreateEmbed::new()
+        .title(format!("{} (MAL ID: {})", details.title, details.mal_id))
+        .url(&details.url)
+        .timestamp(Timestamp::now());
+
+    if let Some(synopsis) = &details.synopsis {
+        embed = embed.description(embed::truncate(synopsis, 1000));
+    }
+
+    if let Some(image_url) = details.images.as_ref().and_then(|i| i.largest_url()) {
+        embed = embed.image(image_url);
+    }
+
+    if let Some(title_english) = &details.title_english {
+        embed = embed.field(locale.t("field-english-title"), title_english, true);
+    }
+    if let Some(title_japanese) = &details.title_japanese {
+        embed = embed.field(locale.t("field-japanese-title"), title_japanese, true);
+    }
+
+    embed = embed.field(locale.t("field-type"), details.manga_type.clone().unwrap_or_else(|| locale.t("not-available")), true);
+    embed = embed.field(locale.t("field-chapters"), details.chapters.map_or(locale.t("not-available"), |c| c.to_string()), true);
+    embed = embed.field(locale.t("field-volumes"), details.volumes.map_or(locale.t("not-available"), |v| v.to_string()), true);
+    embed = embed.field(locale.t("field-status"), details.status.clone().unwrap_or_else(|| locale.t("not-available")), true);
+
+    if let Some(published) = details.published.as_ref().and_then(|p| p.string.as_ref()) {
+        embed = embed.field(locale.t("field-published"), published, true);
+    }
+    if let Some(score) = details.score {
+        embed = embed.field(locale.t("field-score"), format!("{}/10", score), true);
+    }
+    if let Some(rank) = details.rank {
+        embed = embed.field(locale.t("field-rank"), format!("#{}", rank), true);
+    }
+    if let Some(popularity) = details.popularity {
+        embed = embed.field(locale.t("field-popularity"), format!("#{}", popularity), true);
+    }
+    if let Some(members) = details.members {
+        embed = embed.field(locale.t("field-members"), members.to_string(), true);
+    }
+    if let Some(favorites) = details.favorites {
+        embed = embed.field(locale.t("field-favorites"), favorites.to_string(), true);
+    }
+    if !details.genres.is_empty() {
+        let genres: Vec<String> = details.genres.iter().map(|g| format!("[{}]({})", g.name, g.url)).collect();
+        embed = embed.field(locale.t("field-genres"), genres.join(", "), false);
+    }
+
+    embed
+}
+
+fn character_details_embed(details: &CharacterDetails, locale: Locale) -> CreateEmbed {
+    let mut embed = CreateEmbed::new()
+        .title(format!("{} (MAL ID: {})", details.name, details.mal_id))
+        .url(&details.url)
+        .timestamp(Timestamp::now());
+
+    if let Some(about) = &details.about {
+        embed = embed.description(embed::truncate(about, 1000));
+    }
+
+    if let Some(image_url) = details.images.as_ref().and_then(|i| i.largest_url()) {
+        embed = embed.image(image_url);
+    }
+
+    if let Some(name_kanji) = &details.name_kanji {
+        embed = embed.field(locale.t("field-kanji-name"), name_kanji, true);
+    }
+    if let Some(favorites) = details.favorites {
+        embed = embed.field(locale.t("field-favorites"), favorites.to_string(), true);
+    }
+    if !details.nicknames.is_empty() {
+        embed = embed.field(locale.t("field-nicknames"), details.nicknames.join(", "), false);
+    }
+
+    embed
+}
+
+// Rolls `/random/*` until an entry passes the filters, giving up after `MAX_RANDOM_ROLLS` attempts.
+async fn roll_random_embed(options: &RandomOptions, locale: Locale) -> Result<CreateEmbed, String> {
+    for _ in 0..MAX_RANDOM_ROLLS {
+        match options.kind {
+            RandomKind::Anime => match jikan_api::get_random_anime().await {
+                Some(details) if anime_matches(&details, options) => return Ok(anime_details_embed(&details, None, locale)),
+                Some(_) => continue,
+                None => return Err(locale.t("random-anime-error")),
+            },
+            RandomKind::Manga => match jikan_api::get_random_manga().await {
+                Some(details) if manga_matches(&details, options) => return Ok(manga_details_embed(&details, locale)),
+                Some(_) => continue,
+                None => return Err(locale.t("random-manga-error")),
+            },
+            // Characters carry no score, genre or rating information, so the filters don't apply.
+            RandomKind::Character => match jikan_api::get_random_character().await {
+                Some(details) => return Ok(character_details_embed(&details, locale)),
+                None => return Err(locale.t("random-character-error")),
+            },
+        }
+    }
+
+    Err(locale.t_args("random-no-match", &[("rolls", MAX_RANDOM_ROLLS.into())]))
+}
+
+// The re-roll button replays the original arguments; Discord caps custom IDs at 100 characters.
+fn random_reroll_components(args: &str, locale: Locale) -> Vec<CreateActionRow> {
+    let custom_id = format!("{}{}", RANDOM_BUTTON_PREFIX, args);
+    if custom_id.len() > 100 {
+        return Vec::new();
+    }
+    vec![CreateActionRow::Buttons(vec![
+        CreateButton::new(custom_id).label(locale.t("random-reroll")).style(ButtonStyle::Primary),
+    ])]
+}
+
+async fn handle_random_command(ctx: &Context, caller: &Caller, args: &str, prefix: &str, sfw: bool, locale: Locale) {
+    let mut options = match parse_random_options(args, locale) {
+        Ok(options) => options,
+        Err(error) => {
+            let usage = format!("{}random [anime|manga|character] [sfw] [score=<min>] [genre=<name>] [type=<type>]", prefix);
+            if let Err(why) = caller.reply(ctx, locale.t_args("usage-line", &[("problem", error.into()), ("usage", usage.into())])).await {
+                println!("Error sending message: {:?}", why);
+            }
+            return;
+        }
+    };
+    options.sfw |= sfw;
+
+    match roll_random_embed(&options, locale).await {
+        Ok(embed) => send_embed_with_components(ctx, caller, embed, random_reroll_components(args, locale)).await,
+        Err(error) => {
+            let reply = Reply { content: Some(error), components: random_reroll_components(args, locale), ..Reply::default() };
+            if let Err(why) = caller.send(ctx, reply).await {
+                println!("Error sending message: {:?}", why);
+            }
+        }
+    }
+}
+
+async fn handle_random_reroll(ctx: &Context, component: &ComponentInteraction, args: &str) {
+    // Rolling with filters can take longer than Discord's 3 second response window.
+    if let Err(why) = component.defer(&ctx.http).await {
+        println!("Error acknowledging re-roll: {:?}", why);
+        return;
+    }
+
+    let settings = guild_settings::get(component.guild_id).await;
+    let locale = locale_for(component.user.id, &settings).await;
+    let sfw = !allows_adult_content(ctx, component.channel_id, &settings).await;
+    let edit = match parse_random_options(args, locale) {
+        Ok(mut options) => {
+            options.sfw |= sfw;
+            match roll_random_embed(&options, locale).await {
+                Ok(embed) => EditInteractionResponse::new().content("").embed(embed::fit(settings.style(embed))),
+                Err(error) => EditInteractionResponse::new().content(error).embeds(Vec::new()),
+            }
+        },
+        Err(error) => EditInteractionResponse::new().content(error).embeds(Vec::new()),
+    };
+
+    if let Err(why) = component.edit_response(&ctx.http, edit.components(random_reroll_components(args, locale))).await {
+        println!("Error editing re-roll message: {:?}", why);
+    }
+}
+
+// How many shows `!genre <name>` lists.
+const GENRE_TOP_LIMIT: u32 = 10;
+
+fn normalize_name(name: &str) -> String {
+    name.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b_chars.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b_chars.len() + 1];
+        for (j, b_char) in b_chars.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b_chars.len()]
+}
+
+// Exact match first, then prefix, then substring, then the closest name within a small edit distance.
+fn find_genre<'a>(genres: &'a [Genre], query: &str) -> Option<&'a Genre> {
+    let wanted = normalize_name(query);
+    if wanted.is_empty() {
+        return None;
+    }
+
+    let normalized: Vec<(String, &Genre)> = genres.iter().map(|g| (normalize_name(&g.name), g)).collect();
+
+    if let Some((_, genre)) = normalized.iter().find(|(name, _)| *name == wanted) {
+        return Some(genre);
+    }
+    if let Some((_, genre)) = normalized.iter().filter(|(name, _)| name.starts_with(&wanted)).min_by_key(|(name, _)| name.len()) {
+        return Some(genre);
+    }
+    if let Some((_, genre)) = normalized.iter().filter(|(name, _)| name.contains(&wanted)).min_by_key(|(name, _)| name.len()) {
+        return Some(genre);
+    }
+
+    let max_distance = (wanted.chars().count() / 3).max(2);
+    normalized.iter()
+        .map(|(name, genre)| (levenshtein(name, &wanted), *genre))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, genre)| genre)
+}
+
+async fn handle_genres_command(ctx: &Context, caller: &Caller, prefix: &str, locale: Locale) {
+    let mut description = String::new();
+    for (filter, heading) in [("genres", "genres-heading-genres"), ("themes", "genres-heading-themes"), ("demographics", "genres-heading-demographics")] {
+        match jikan_api::get_anime_genres(Some(filter)).await {
+            Some(genres) => {
+                let entries: Vec<String> = genres.iter().map(|g| format!("{} ({})", g.name, g.count)).collect();
+                description.push_str(&format!("**{}**\n{}\n\n", locale.t(heading), entries.join(", ")));
+            },
+            None => {
+                if let Err(why) = caller.say(ctx, locale.t("genres-error")).await {
+                    println!("Error sending message: {:?}", why);
+                }
+                return;
+            }
+        }
+    }
+
+    let embed = CreateEmbed::new()
+        .title(locale.t("genres-title"))
+        .description(description)
+        .footer(CreateEmbedFooter::new(locale.t_args("genres-footer", &[("prefix", prefix.into())])))
+        .timestamp(Timestamp::now());
+    send_embed_message(ctx, caller, embed).await;
+}
+
+async fn handle_genre_command(ctx: &Context, caller: &Caller, query: &str, prefix: &str, sfw: bool, locale: Locale) {
+    let genres = match jikan_api::get_anime_genres(None).await {
+        Some(genres) => genres,
+        None => {
+            if let Err(why) = caller.say(ctx, locale.t("genres-error")).await {
+                println!("Error sending message: {:?}", why);
+            }
+            return;
+        }
+    };
+
+    let genre = match find_genre(&genres, query) {
+        Some(genre) => genre,
+        None => {
+            if let Err(why) = caller.reply(ctx, locale.t_args("genre-no-match", &[("query", query.into()), ("prefix", prefix.into())])).await {
+                println!("Error sending message: {:?}", why);
+            }
+            return;
+        }
+    };
+
+    match jikan_api::search_anime_by_genre(genre.mal_id, GENRE_TOP_LIMIT, sfw).await {
+        Some(animes) if !animes.is_empty() => {
+            let mut description = String::new();
+            for (i, anime) in animes.iter().enumerate() {
+                let score = anime.score.map_or(locale.t("not-available"), |s| format!("{}/10", s));
+                description.push_str(&format!("{}. {} (MAL ID: {}) - {}\n", i + 1, anime.title, anime.mal_id, score));
+            }
+
+            let embed = CreateEmbed::new()
+                .title(locale.t_args("genre-title", &[("genre", genre.name.as_str().into())]))
+                .url(&genre.url)
+                .description(description)
+                .footer(CreateEmbedFooter::new(locale.t_args("genre-footer", &[("count", genre.count.into()), ("prefix", prefix.into())])))
+                .timestamp(Timestamp::now());
+            send_embed_message(ctx, caller, embed).await;
+        },
+        Some(_) => {
+            if let Err(why) = caller.say(ctx, locale.t_args("genre-empty", &[("genre", genre.name.as_str().into())])).await {
+                println!("Error sending message: {:?}", why);
+            }
+        },
+        None => {
+            if let Err(why) = caller.say(ctx, locale.t("genre-error")).await {
+                println!("Error sending message: {:?}", why);
+            }
+        }
+    }
+}
+
+// How many anime one page of `!studio` lists.
+const STUDIO_PAGE_SIZE: u32 = 10;
+const STUDIO_BUTTON_PREFIX: &str = "studio:";
+
+// Prefers a producer whose name matches the query exactly; otherwise the one with the most entries.
+fn pick_producer(producers: Vec<Producer>, query: &str) -> Option<Producer> {
+    let wanted = normalize_name(query);
+    let exact = producers.iter().position(|p| p.titles.iter().any(|t| normalize_name(&t.title) == wanted));
+    let index = exact.unwrap_or(0);
+    producers.into_iter().nth(index)
+}
+
+async fn studio_page(producer_id: u32, page: u32, sfw: bool, locale: Locale) -> Result<(CreateEmbed, Vec<CreateActionRow>), String> {
+    let first_rank = first_index(page, STUDIO_PAGE_SIZE).ok_or_else(|| locale.t_args("page-invalid", &[("page", page.into())]))?;
+    let producer = jikan_api::get_producer_full(producer_id).await
+        .ok_or_else(|| locale.t_args("studio-fetch-error", &[("id", producer_id.into())]))?;
+    let animes = jikan_api::search_anime_by_producer(producer_id, page, STUDIO_PAGE_SIZE, sfw).await
+        .ok_or_else(|| locale.t_args("studio-anime-error", &[("name", producer.name().into())]))?;
+
+    let mut embed = CreateEmbed::new()
+        .title(producer.name())
+        .url(&producer.url)
+        .timestamp(Timestamp::now());
+
+    if let Some(about) = &producer.about {
+        embed = embed.description(embed::truncate(about, 1000));
+    }
+    if let Some(image_url) = producer.images.as_ref().and_then(|i| i.largest_url()) {
+        embed = embed.thumbnail(image_url);
+    }
+    if let Some(established) = &producer.established {
+        // Jikan sends a full timestamp; the date part is all that matters here.
+        embed = embed.field(locale.t("field-established"), established.get(..10).unwrap_or(established), true);
+    }
+    if let Some(favorites) = producer.favorites {
+        embed = embed.field(locale.t("field-favorites"), favorites.to_string(), true);
+    }
+    if let Some(count) = producer.count {
+        embed = embed.field(locale.t("field-anime"), count.to_string(), true);
+    }
+    if !producer.external.is_empty() {
+        let links: Vec<String> = producer.external.iter().take(5).map(|l| format!("[{}]({})", l.name, l.url)).collect();
+        embed = embed.field(locale.t("field-links"), links.join(", "), false);
+    }
+
+    let mut listing = String::new();
+    for (i, anime) in animes.data.iter().enumerate() {
+        let score = anime.score.map_or(locale.t("not-available"), |s| format!("{}/10", s));
+        listing.push_str(&format!("{}. {} (MAL ID: {}) - {}\n", first_rank + i as u32 + 1, anime.title, anime.mal_id, score));
+    }
+    if listing.is_empty() {
+        listing.push_str(&locale.t("studio-page-empty"));
+    }
+    embed = embed.field(locale.t("studio-anime-by-score"), listing, false);
+
+    let last_page = animes.pagination.as_ref().map_or(page, |p| p.last_visible_page.max(1));
+    embed = embed.footer(CreateEmbedFooter::new(locale.t_args("page-of", &[("page", page.into()), ("total", last_page.into())])));
+
+    let base_id = format!("{}{}:", STUDIO_BUTTON_PREFIX, producer_id);
+    Ok((embed, page_buttons(&base_id, page, page < last_page, locale)))
+}
+
+async fn handle_studio_command(ctx: &Context, caller: &Caller, query: &str, sfw: bool, locale: Locale) {
+    let producer = match jikan_api::search_producers(query).await {
+        Some(producers) => match pick_producer(producers, query) {
+            Some(producer) => producer,
+            None => {
+                if let Err(why) = caller.say(ctx, locale.t_args("studio-not-found", &[("query", query.into())])).await {
+                    println!("Error sending message: {:?}", why);
+                }
+                return;
+            }
+        },
+        None => {
+            if let Err(why) = caller.say(ctx, locale.t("studio-search-error")).await {
+                println!("Error sending message: {:?}", why);
+            }
+            return;
+        }
+    };
+
+    match studio_page(producer.mal_id, 1, sfw, locale).await {
+        Ok((embed, components)) => send_embed_with_components(ctx, caller, embed, components).await,
+        Err(error) => {
+            if let Err(why) = caller.say(ctx, error).await {
+                println!("Error sending message: {:?}", why);
+            }
+        }
+    }
+}
+
+async fn handle_studio_page_button(ctx: &Context, component: &ComponentInteraction, args: &str) {
+    let Some((producer_id, page)) = args.split_once(':')
+        .and_then(|(id, page)| Some((id.parse::<u32>().ok()?, page.parse::<u32>().ok()?)))
+        .filter(|(_, page)| *page >= 1) else {
+        return;
+    };
+
+    if let Err(why) = component.defer(&ctx.http).await {
+        println!("Error acknowledging studio page: {:?}", why);
+        return;
+    }
+
+    let settings = guild_settings::get(component.guild_id).await;
+    let locale = locale_for(component.user.id, &settings).await;
+    let sfw = !allows_adult_content(ctx, component.channel_id, &settings).await;
+    let edit = match studio_page(producer_id, page, sfw, locale).await {
+        Ok((embed, components)) => EditInteractionResponse::new().embed(embed::fit(settings.style(embed))).components(components),
+        Err(error) => EditInteractionResponse::new().content(error),
+    };
+    if let Err(why) = component.edit_response(&ctx.http, edit).await {
+        println!("Error editing studio message: {:?}", why);
+    }
+}
+
+// Limits for `!anime watchorder`: how far from the starting entry to walk, and how many uncached Jikan requests to spend.
+const WATCH_ORDER_MAX_DEPTH: usize = 6;
+const WATCH_ORDER_REQUEST_BUDGET: usize = 25;
+// How many related entries `!anime related` looks up where adult entries are hidden. The rest are
+// left out, since they can't be checked.
+const RELATED_CHECK_LIMIT: usize = 25;
+
+// Whether a related entry is adult, or couldn't be looked up to tell.
+async fn is_adult_entry(entry: &MalUrl) -> bool {
+    match entry.entry_type.as_str() {
+        "anime" => jikan_api::get_anime_details_by_id(entry.mal_id).await.is_none_or(|details| details.is_adult()),
+        "manga" => jikan_api::get_manga_details_by_id(entry.mal_id).await.is_none_or(|details| details.is_adult()),
+        _ => false,
+    }
+}
+
+async fn handle_anime_related_command(ctx: &Context, caller: &Caller, mal_id: u32, prefix: &str, sfw: bool, locale: Locale) {
+    match jikan_api::get_anime_relations(mal_id).await {
+        Some(relations) if relations.is_empty() => {
+            if let Err(why) = caller.say(ctx, locale.t_args("related-none", &[("id", mal_id.into())])).await {
+                println!("Error sending message: {:?}", why);
+            }
+        },
+        Some(relations) => {
+            let mut embed = CreateEmbed::new()
+                .title(locale.t_args("related-title", &[("id", mal_id.into())]))
+                .timestamp(Timestamp::now());
+
+            // Relations only carry names and IDs, so where adult entries are hidden each one is looked up.
+            let mut checked = 0;
+            let mut hidden = 0;
+            for relation in relations.iter().take(25) {
+                let mut lines = Vec::new();
+                for e in &relation.entry {
+                    if sfw {
+                        checked += 1;
+                        if checked > RELATED_CHECK_LIMIT || is_adult_entry(e).await {
+                            hidden += 1;
+                            continue;
+                        }
+                    }
+                    lines.push(format!("[{}]({}) ({}, MAL ID: {})", e.name, e.url, e.entry_type, e.mal_id));
+                }
+                if !lines.is_empty() {
+                    embed = embed.field(&relation.relation, join_lines_limited(&lines, 1024, locale), false);
+                }
+            }
+            let mut footer = locale.t_args("related-footer", &[("prefix", prefix.into())]);
+            if hidden > 0 {
+                footer.push('\n');
+                footer.push_str(&locale.t_args("adult-entries-hidden", &[("count", hidden.into())]));
+            }
+            embed = embed.footer(CreateEmbedFooter::new(footer));
+
+            send_embed_message(ctx, caller, embed).await;
+        },
+        None => {
+            if let Err(why) = caller.say(ctx, locale.t_args("related-error", &[("id", mal_id.into())])).await {
+                println!("Error sending message: {:?}", why);
+            }
+        }
+    }
+}
+
+async fn watch_order_reply(mal_id: u32, sfw: bool, locale: Locale) -> Result<CreateEmbed, String> {
+    match jikan_api::walk_franchise(mal_id, WATCH_ORDER_MAX_DEPTH, WATCH_ORDER_REQUEST_BUDGET).await {
+        Some(walk) => {
+            let entries: Vec<&AnimeDetails> = walk.entries.iter().filter(|details| !(sfw && details.is_adult())).collect();
+            let hidden = walk.entries.len() - entries.len();
+            let lines: Vec<String> = entries.iter().enumerate().map(|(i, details)| {
+                let anime_type = details.anime_type.clone().unwrap_or_else(|| locale.t("not-available"));
+                let aired = details.aired.from.as_deref().and_then(|from| from.get(..10)).map_or_else(|| locale.t("not-aired-yet"), str::to_string);
+                let episodes = details.episodes.map_or(String::new(), |e| format!(", {}", locale.t_args("watchorder-episodes", &[("count", e.into())])));
+                format!("{}. [{}]({}) ({}{}, {})", i + 1, details.title, details.url, anime_type, episodes, aired)
+            }).collect();
+
+            let mut footer = locale.t_args("watchorder-footer", &[("count", entries.len().into())]);
+            if walk.incomplete {
+                footer.push(' ');
+                footer.push_str(&locale.t("watchorder-incomplete"));
+            }
+            if hidden > 0 {
+                footer.push(' ');
+                footer.push_str(&locale.t_args("adult-entries-hidden", &[("count", hidden.into())]));
+            }
+
+            Ok(CreateEmbed::new()
+                .title(locale.t_args("watchorder-title", &[("id", mal_id.into())]))
+                .description(join_lines_limited(&lines, 4096, locale))
+                .footer(CreateEmbedFooter::new(footer))
+                .timestamp(Timestamp::now()))
+        },
+        None => Err(locale.t_args("watchorder-error", &[("id", mal_id.into())])),
+    }
+}
+
+// Walking a franchise can take a while, so a placeholder saying so is edited into the result.
+async fn handle_anime_watch_order_command(ctx: &Context, caller: &Caller, mal_id: u32, sfw: bool, locale: Locale) {
+    let placeholder = locale.t_args("watchorder-building", &[("id", mal_id.into())]);
+    send_with_placeholder(ctx, caller, placeholder, watch_order_reply(mal_id, sfw, locale)).await;
+}
+
+// `!anime episodes` shows this many episodes per page; Jikan's own pages hold 100.
+const EPISODES_PAGE_SIZE: u32 = 25;
+const JIKAN_EPISODES_PER_PAGE: u32 = 100;
+const EPISODES_BUTTON_PREFIX: &str = "episodes:";
+
+async fn episodes_page(mal_id: u32, page: u32, prefix: &str, locale: Locale) -> Result<(CreateEmbed, Vec<CreateActionRow>), String> {
+    let first = first_index(page, EPISODES_PAGE_SIZE).ok_or_else(|| locale.t_args("page-invalid", &[("page", page.into())]))?;
+    let jikan_page = first / JIKAN_EPISODES_PER_PAGE + 1;
+    let offset = (first % JIKAN_EPISODES_PER_PAGE) as usize;
+
+    let response = jikan_api::get_anime_episodes(mal_id, jikan_page).await
+        .ok_or_else(|| locale.t_args("episodes-error", &[("id", mal_id.into())]))?;
+    let episodes: Vec<&jikan_api::Episode> = response.data.iter().skip(offset).take(EPISODES_PAGE_SIZE as usize).collect();
+    if episodes.is_empty() {
+        return Err(locale.t_args("episodes-none", &[("page", page.into()), ("id", mal_id.into())]));
+    }
+    let has_next = offset + episodes.len() < response.data.len() || response.pagination.has_next_page;
+
+    let lines: Vec<String> = episodes.iter().map(|episode| {
+        let title = match &episode.url {
+            Some(url) => format!("[{}]({})", episode.title, url),
+            None => episode.title.clone(),
+        };
+        let aired = episode.aired.as_deref().and_then(|a| a.get(..10)).map_or_else(|| locale.t("not-aired-yet"), str::to_string);
+        let score = episode.score.map_or(locale.t("not-available"), |s| format!("{:.2}/5", s));
+        let mut line = format!("**{}.** {} - {} - {}", episode.mal_id, title, aired, score);
+        if episode.filler {
+            line.push(' ');
+            line.push_str(&locale.t("episodes-filler-tag"));
+        }
+        if episode.recap {
+            line.push(' ');
+            line.push_str(&locale.t("episodes-recap-tag"));
+        }
+        line
+    }).collect();
+
+    let filler_count = episodes.iter().filter(|e| e.filler).count();
+    let recap_count = episodes.iter().filter(|e| e.recap).count();
+
+    let embed = CreateEmbed::new()
+        .title(locale.t_args("episodes-title", &[("id", mal_id.into())]))
+        .description(join_lines_limited(&lines, 4096, locale))
+        .footer(CreateEmbedFooter::new(locale.t_args("episodes-footer", &[
+            ("page", page.into()),
+            ("filler", filler_count.into()),
+            ("recap", recap_count.into()),
+            ("prefix", prefix.into()),
+        ])))
+        .timestamp(Timestamp::now());
+
+    let base_id = format!("{}{}:", EPISODES_BUTTON_PREFIX, mal_id);
+    Ok((embed, page_buttons(&base_id, page, has_next, locale)))
+}
+
+async fn handle_anime_episodes_command(ctx: &Context, caller: &Caller, mal_id: u32, page: u32, prefix: &str, locale: Locale) {
+    match episodes_page(mal_id, page, prefix, locale).await {
+        Ok((embed, components)) => send_embed_with_components(ctx, caller, embed, components).await,
+        Err(error) => {
+            if let Err(why) = caller.say(ctx, error).await {
+                println!("Error sending message: {:?}", why);
+            }
+        }
+    }
+}
+
+async fn handle_episodes_page_button(ctx: &Context, component: &ComponentInteraction, args: &str) {
+    let Some((mal_id, page)) = args.split_once(':')
+        .and_then(|(id, page)| Some((id.parse::<u32>().ok()?, page.parse::<u32>().ok()?)))
+        .filter(|(_, page)| *page >= 1) else {
+        return;
+    };
+
+    if let Err(why) = component.defer(&ctx.http).await {
+        println!("Error acknowledging episodes page: {:?}", why);
+        return;
+    }
+
+    let settings = guild_settings::get(component.guild_id).await;
+    let locale = locale_for(component.user.id, &settings).await;
+    let edit = match episodes_page(mal_id, page, &settings.prefix, locale).await {
+        Ok((embed, components)) => EditInteractionResponse::new().embed(embed::fit(settings.style(embed))).components(components),
+        Err(error) => EditInteractionResponse::new().content(error),
+    };
+    if let Err(why) = component.edit_response(&ctx.http, edit).await {
+        println!("Error editing episodes message: {:?}", why);
+    }
+}
+
+async fn handle_anime_episode_command(ctx: &Context, caller: &Caller, mal_id: u32, episode: u32, locale: Locale) {
+    match jikan_api::get_anime_episode(mal_id, episode).await {
+        Some(details) => {
+            let mut embed = CreateEmbed::new()
+                .title(locale.t_args("episode-title", &[("number", details.mal_id.into()), ("title", details.title.as_str().into())]))
+                .url(&details.url)
+                .timestamp(Timestamp::now());
+
+            if let Some(synopsis) = &details.synopsis {
+                embed = embed.description(embed::truncate(synopsis, 1000));
+            }
+            if let Some(title_japanese) = &details.title_japanese {
+                embed = embed.field(locale.t("field-japanese-title"), title_japanese, true);
+            }
+            if let Some(title_romanji) = &details.title_romanji {
+                embed = embed.field(locale.t("field-romanized-title"), title_romanji, true);
+            }
+            if let Some(aired) = details.aired.as_deref().and_then(|a| a.get(..10)) {
+                embed = embed.field(locale.t("field-aired"), aired, true);
+            }
+            if let Some(duration) = details.duration {
+                embed = embed.field(locale.t("field-duration"), locale.t_args("episode-minutes", &[("minutes", (duration / 60).into())]), true);
+            }
+            embed = embed.field(locale.t("field-filler"), if details.filler { locale.t("yes") } else { locale.t("no") }, true);
+            embed = embed.field(locale.t("field-recap"), if details.recap { locale.t("yes") } else { locale.t("no") }, true);
+            embed = embed.footer(CreateEmbedFooter::new(format!("MAL ID: {}", mal_id)));
+
+            send_embed_message(ctx, caller, embed).await;
+        },
+        None => {
+            if let Err(why) = caller.say(ctx, locale.t_args("episode-not-found", &[("episode", episode.into()), ("id", mal_id.into())])).await {
+                println!("Error sending message: {:?}", why);
+            }
+        }
+    }
+}
+
+// Accepts either a MAL ID or a name; names use the first search result.
+async fn handle_anime_where_command(ctx: &Context, caller: &Caller, query: &str, sfw: bool, locale: Locale) {
+    let (mal_id, title) = match query.parse::<u32>() {
+        Ok(mal_id) => match jikan_api::get_anime_details_by_id(mal_id).await {
+            Some(details) => {
+                if let Err(refusal) = adult_refusal(&details, sfw, locale) {
+                    if let Err(why) = caller.reply(ctx, refusal).await {
+                        println!("Error sending message: {:?}", why);
+                    }
+                    return;
+                }
+                (mal_id, details.title)
+            },
+            None => {
+                if let Err(why) = caller.say(ctx, locale.t_args("details-not-found", &[("id", mal_id.into())])).await {
                     println!("Error sending message: {:?}", why);
                 }
                 return;
             }
-            let mal_id_str = parts[2].trim();
-            let mal_id = match mal_id_str.parse::<u32>() {
-                Ok(id) => id,
-                Err(_) => {
-                    if let Err(why) = msg.reply(&ctx.http, "Invalid MAL ID. Please provide a numeric ID.").await {
-                        println!("Error sending message: {:?}", why);
-                    }
+        },
+        Err(_) => match jikan_api::search_anime(query, sfw).await.and_then(|animes| animes.into_iter().next()) {
+            Some(anime) => (anime.mal_id, anime.title),
+            None => {
+                if let Err(why) = caller.say(ctx, locale.t_args("search-no-results", &[("query", query.into())])).await {
+                    println!("Error sending message: {:?}", why);
+                }
+                return;
+            }
+        },
+    };
+
+    let links = fetch_watch_links(mal_id).await;
+    let mut embed = CreateEmbed::new()
+        .title(locale.t_args("where-title", &[("title", title.into()), ("id", mal_id.into())]))
+        .footer(CreateEmbedFooter::new(region_note(locale)))
+        .timestamp(Timestamp::now());
+
+    if links.streaming.is_empty() {
+        embed = embed.field(locale.t("field-streaming"), locale.t("where-no-streaming"), false);
+    } else {
+        embed = embed.field(locale.t("field-streaming"), format_links(&links.streaming, locale), false);
+    }
+    if !links.external.is_empty() {
+        embed = embed.field(locale.t("field-official-links"), format_links(&links.external, locale), false);
+    }
+
+    send_embed_message(ctx, caller, embed).await;
+}
+
+// Standard deviations of the score distribution below/above which `!anime stats` calls it a consensus or divisive.
+const CONSENSUS_SPREAD: f64 = 1.3;
+const DIVISIVE_SPREAD: f64 = 1.9;
+
+fn spread_verdict(votes: &[u32; 10], locale: Locale) -> Option<String> {
+    let total: f64 = votes.iter().map(|&v| v as f64).sum();
+    if total == 0.0 {
+        return None;
+    }
+    let mean = votes.iter().enumerate().map(|(i, &v)| (i + 1) as f64 * v as f64).sum::<f64>() / total;
+    let variance = votes.iter().enumerate().map(|(i, &v)| ((i + 1) as f64 - mean).powi(2) * v as f64).sum::<f64>() / total;
+    let spread = variance.sqrt();
+
+    let verdict = if spread < CONSENSUS_SPREAD {
+        locale.t("stats-consensus")
+    } else if spread > DIVISIVE_SPREAD {
+        locale.t("stats-divisive")
+    } else {
+        locale.t("stats-mixed")
+    };
+    let summary = locale.t_args("stats-spread", &[("mean", format!("{:.2}", mean).into()), ("spread", format!("{:.2}", spread).into())]);
+    Some(format!("{}\n{}", summary, verdict))
+}
+
+async fn handle_anime_stats_command(ctx: &Context, caller: &Caller, mal_id: u32, locale: Locale) {
+    let statistics = match jikan_api::get_anime_statistics(mal_id).await {
+        Some(statistics) => statistics,
+        None => {
+            if let Err(why) = caller.say(ctx, locale.t_args("stats-not-found", &[("id", mal_id.into())])).await {
+                println!("Error sending message: {:?}", why);
+            }
+            return;
+        }
+    };
+    let title = jikan_api::get_anime_details_by_id(mal_id).await
+        .map_or_else(|| format!("MAL ID: {}", mal_id), |details| details.title);
+
+    let mut votes = [0u32; 10];
+    for entry in &statistics.scores {
+        if (1..=10).contains(&entry.score) {
+            votes[entry.score as usize - 1] = entry.votes;
+        }
+    }
+
+    let mut embed = CreateEmbed::new()
+        .title(locale.t_args("stats-title", &[("title", title.into())]))
+        .field(locale.t("field-watching"), statistics.watching.to_string(), true)
+        .field(locale.t("field-completed"), statistics.completed.to_string(), true)
+        .field(locale.t("field-on-hold"), statistics.on_hold.to_string(), true)
+        .field(locale.t("field-dropped"), statistics.dropped.to_string(), true)
+        .field(locale.t("field-plan-to-watch"), statistics.plan_to_watch.to_string(), true)
+        .field(locale.t("field-total"), statistics.total.to_string(), true)
+        .timestamp(Timestamp::now());
+    if let Some(verdict) = spread_verdict(&votes, locale) {
+        embed = embed.description(verdict);
+    }
+
+    let mut attachment = None;
+    match chart::render_score_chart(&votes) {
+        Ok(png_bytes) => {
+            embed = embed.image("attachment://scores.png");
+            attachment = Some(CreateAttachment::bytes(png_bytes, "scores.png"));
+        },
+        Err(e) => eprintln!("Error rendering score chart for ID {}: {}", mal_id, e),
+    }
+
+    let embed = embed::fit(guild_settings::get(caller.guild_id).await.style(embed));
+    if let Err(why) = caller.send(ctx, Reply { embed: Some(embed), attachment, ..Reply::default() }).await {
+        println!("Error sending message: {:?}", why);
+    }
+}
+
+// Jikan returns 20 reviews per page; `!anime reviews` shows one review at a time.
+const JIKAN_REVIEWS_PER_PAGE: u32 = 20;
+const REVIEW_EXCERPT_CHARS: usize = 700;
+// Discord rejects messages longer than 2000 characters; leave room for the spoiler markup.
+const DM_CHUNK_CHARS: usize = 1900;
+const REVIEWS_BUTTON_PREFIX: &str = "reviews:";
+const REVIEW_FULL_BUTTON_PREFIX: &str = "reviewfull:";
+
+// Wraps text in Discord spoiler markup. A `||` inside the text would close the spoiler early, so it's broken up.
+fn spoiler(text: &str) -> String {
+    format!("||{}||", text.replace("||", "| |"))
+}
+
+// Splits text into chunks of at most `max_chars` characters, preferring paragraph, line and word boundaries.
+fn split_message(text: &str, max_chars: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut rest = text.trim();
+    while !rest.is_empty() {
+        let Some((limit, _)) = rest.char_indices().nth(max_chars) else {
+            chunks.push(rest.to_string());
+            break;
+        };
+        let window = &rest[..limit];
+        let cut = ["\n\n", "\n", " "].iter()
+            .find_map(|separator| window.rfind(separator).filter(|&at| at > 0))
+            .unwrap_or(limit);
+        chunks.push(rest[..cut].trim_end().to_string());
+        rest = rest[cut..].trim_start();
+    }
+    chunks
+}
+
+// Fetches the review at the 1-based position `number`, plus whether another one follows it.
+async fn fetch_review(mal_id: u32, number: u32, locale: Locale) -> Result<(jikan_api::Review, bool), String> {
+    let index = number - 1;
+    let jikan_page = index / JIKAN_REVIEWS_PER_PAGE + 1;
+    let offset = (index % JIKAN_REVIEWS_PER_PAGE) as usize;
+
+    let response = jikan_api::get_anime_reviews(mal_id, jikan_page).await
+        .ok_or_else(|| locale.t_args("reviews-error", &[("id", mal_id.into())]))?;
+    let has_next = offset + 1 < response.data.len() || response.pagination.has_next_page;
+    let review = response.data.into_iter().nth(offset)
+        .ok_or_else(|| locale.t_args("reviews-none", &[("id", mal_id.into())]))?;
+    Ok((review, has_next))
+}
+
+async fn review_page(mal_id: u32, number: u32, locale: Locale) -> Result<(CreateEmbed, Vec<CreateActionRow>), String> {
+    let (review, has_next) = fetch_review(mal_id, number, locale).await?;
+    let title = jikan_api::get_anime_details_by_id(mal_id).await
+        .map_or_else(|| format!("MAL ID: {}", mal_id), |details| details.title);
+
+    let excerpt = embed::truncate(review.review.trim(), REVIEW_EXCERPT_CHARS);
+    let mut author = CreateEmbedAuthor::new(&review.user.username).url(&review.user.url);
+    if let Some(avatar) = review.user.images.as_ref().and_then(|i| i.jpg.as_ref()).and_then(|j| j.image_url.as_ref()) {
+        author = author.icon_url(avatar);
+    }
+
+    let mut badges = review.tags.clone();
+    if review.is_preliminary {
+        badges.push(locale.t("review-preliminary"));
+    }
+    if review.is_spoiler {
+        badges.push(locale.t("review-spoilers"));
+    }
+
+    let mut embed = CreateEmbed::new()
+        .title(locale.t_args("review-title", &[("title", title.into())]))
+        .url(&review.url)
+        .author(author)
+        .description(if review.is_spoiler { spoiler(&excerpt) } else { excerpt })
+        .field(locale.t("field-score"), review.score.map_or(locale.t("not-available"), |s| format!("{}/10", s)), true)
+        .field(locale.t("field-date"), review.date.get(..10).unwrap_or(&review.date), true)
+        .footer(CreateEmbedFooter::new(locale.t_args("review-footer", &[("number", number.into())])))
+        .timestamp(Timestamp::now());
+    if !badges.is_empty() {
+        embed = embed.field(locale.t("field-tags"), badges.join(", "), true);
+    }
+    if let Some(episodes_watched) = review.episodes_watched {
+        embed = embed.field(locale.t("field-episodes-watched"), episodes_watched.to_string(), true);
+    }
+
+    let base_id = format!("{}{}:", REVIEWS_BUTTON_PREFIX, mal_id);
+    let mut components = page_buttons(&base_id, number, has_next, locale);
+    components.push(CreateActionRow::Buttons(vec![
+        CreateButton::new(format!("{}{}:{}", REVIEW_FULL_BUTTON_PREFIX, mal_id, number))
+            .label(locale.t("review-full-button"))
+            .style(ButtonStyle::Primary),
+    ]));
+    Ok((embed, components))
+}
+
+async fn handle_anime_reviews_command(ctx: &Context, caller: &Caller, mal_id: u32, locale: Locale) {
+    match review_page(mal_id, 1, locale).await {
+        Ok((embed, components)) => send_embed_with_components(ctx, caller, embed, components).await,
+        Err(error) => {
+            if let Err(why) = caller.say(ctx, error).await {
+                println!("Error sending message: {:?}", why);
+            }
+        }
+    }
+}
+
+// Button arguments look like `<MAL_ID>:<review number>`.
+fn parse_review_button(args: &str) -> Option<(u32, u32)> {
+    args.split_once(':')
+        .and_then(|(id, number)| Some((id.parse::<u32>().ok()?, number.parse::<u32>().ok()?)))
+        .filter(|(_, number)| *number >= 1)
+}
+
+async fn handle_reviews_page_button(ctx: &Context, component: &ComponentInteraction, args: &str) {
+    let Some((mal_id, number)) = parse_review_button(args) else {
+        return;
+    };
+
+    if let Err(why) = component.defer(&ctx.http).await {
+        println!("Error acknowledging reviews page: {:?}", why);
+        return;
+    }
+
+    let settings = guild_settings::get(component.guild_id).await;
+    let locale = locale_for(component.user.id, &settings).await;
+    let edit = match review_page(mal_id, number, locale).await {
+        Ok((embed, components)) => EditInteractionResponse::new().embed(embed::fit(settings.style(embed))).components(components),
+        Err(error) => EditInteractionResponse::new().content(error),
+    };
+    if let Err(why) = component.edit_response(&ctx.http, edit).await {
+        println!("Error editing reviews message: {:?}", why);
+    }
+}
+
+async fn handle_review_full_button(ctx: &Context, component: &ComponentInteraction, args: &str) {
+    let Some((mal_id, number)) = parse_review_button(args) else {
+        return;
+    };
+
+    if let Err(why) = component.defer_ephemeral(&ctx.http).await {
+        println!("Error acknowledging full review request: {:?}", why);
+        return;
+    }
+
+    let locale = locale_for(component.user.id, &guild_settings::get(component.guild_id).await).await;
+    let reply = match fetch_review(mal_id, number, locale).await {
+        Ok((review, _)) => {
+            let heading = locale.t_args("review-dm-header", &[("user", review.user.username.as_str().into()), ("id", mal_id.into())]);
+            let header = format!("{}\n<{}>", heading, review.url);
+            let mut delivered = component.user.direct_message(&ctx.http, CreateMessage::new().content(header)).await.is_ok();
+            // Each chunk gets its own spoiler markup because a spoiler can't span messages.
+            for chunk in split_message(&review.review, DM_CHUNK_CHARS) {
+                if !delivered {
+                    break;
+                }
+                let content = if review.is_spoiler { spoiler(&chunk) } else { chunk };
+                delivered = component.user.direct_message(&ctx.http, CreateMessage::new().content(content)).await.is_ok();
+            }
+            if delivered { locale.t("review-dm-sent") } else { locale.t("review-dm-failed") }
+        },
+        Err(error) => error,
+    };
+
+    if let Err(why) = component.edit_response(&ctx.http, EditInteractionResponse::new().content(reply)).await {
+        println!("Error answering full review request: {:?}", why);
+    }
+}
+
+// How many articles `!anime news` lists.
+const NEWS_LIST_LIMIT: usize = 5;
+
+async fn handle_anime_news_command(ctx: &Context, caller: &Caller, mal_id: u32, prefix: &str, locale: Locale) {
+    match jikan_api::get_anime_news(mal_id).await {
+        Some(articles) if articles.is_empty() => {
+            if let Err(why) = caller.say(ctx, locale.t_args("news-none", &[("id", mal_id.into())])).await {
+                println!("Error sending message: {:?}", why);
+            }
+        },
+        Some(articles) => {
+            let mut embed = CreateEmbed::new()
+                .title(locale.t_args("news-title", &[("id", mal_id.into())]))
+                .timestamp(Timestamp::now());
+
+            for article in articles.iter().take(NEWS_LIST_LIMIT) {
+                let mut value = article.date.get(..10).unwrap_or(&article.date).to_string();
+                if let Some(author) = &article.author_username {
+                    value.push(' ');
+                    value.push_str(&locale.t_args("news-by", &[("author", author.as_str().into())]));
+                }
+                if let Some(excerpt) = &article.excerpt {
+                    value.push_str(&format!("\n{}", embed::truncate(excerpt, 200)));
+                }
+                value.push_str(&format!("\n[{}]({})", locale.t("news-read-more"), article.url));
+                embed = embed.field(embed::truncate(&article.title, 250), value, false);
+            }
+            embed = embed.footer(CreateEmbedFooter::new(locale.t_args("news-footer", &[("prefix", prefix.into())])));
+
+            send_embed_message(ctx, caller, embed).await;
+        },
+        None => {
+            if let Err(why) = caller.say(ctx, locale.t_args("news-error", &[("id", mal_id.into())])).await {
+                println!("Error sending message: {:?}", why);
+            }
+        }
+    }
+}
+
+// Whether Discord marks the channel, or the channel a thread lives in, as age-restricted. DMs aren't.
+async fn is_age_restricted(ctx: &Context, channel_id: ChannelId) -> bool {
+    let channel = match channel_id.to_channel(&ctx.http).await {
+        Ok(Channel::Guild(channel)) => channel,
+        Ok(_) => return false,
+        Err(why) => {
+            println!("Error fetching channel {}: {:?}", channel_id, why);
+            return false;
+        }
+    };
+    match channel.parent_id.filter(|_| channel.thread_metadata.is_some()) {
+        Some(parent_id) => matches!(parent_id.to_channel(&ctx.http).await, Ok(Channel::Guild(parent)) if parent.nsfw),
+        None => channel.nsfw,
+    }
+}
+
+// Whether the server's `nsfw` setting lets Rx-rated and Hentai entries show up in this channel.
+async fn allows_adult_content(ctx: &Context, channel_id: ChannelId, settings: &GuildSettings) -> bool {
+    match settings.nsfw {
+        NsfwPolicy::Allow => true,
+        NsfwPolicy::Block => false,
+        NsfwPolicy::AgeRestricted => is_age_restricted(ctx, channel_id).await,
+    }
+}
+
+// Guild admins are the owner and members whose roles grant Administrator or Manage Server.
+async fn is_guild_admin(ctx: &Context, caller: &Caller) -> bool {
+    let Some(guild_id) = caller.guild_id else {
+        return false;
+    };
+    let guild = match guild_id.to_partial_guild(&ctx.http).await {
+        Ok(guild) => guild,
+        Err(why) => {
+            println!("Error fetching guild {}: {:?}", guild_id, why);
+            return false;
+        }
+    };
+    if guild.owner_id == caller.user_id {
+        return true;
+    }
+    let member = match guild_id.member(&ctx.http, caller.user_id).await {
+        Ok(member) => member,
+        Err(why) => {
+            println!("Error fetching member {}: {:?}", caller.user_id, why);
+            return false;
+        }
+    };
+
+    // The @everyone role shares its ID with the guild.
+    let everyone = RoleId::new(guild_id.get());
+    member.roles.iter().chain(std::iter::once(&everyone))
+        .filter_map(|role_id| guild.roles.get(role_id))
+        .any(|role| role.permissions.administrator() || role.permissions.manage_guild())
+}
+
+// Heavy commands follow the role lists from `!perms`; admins can always use them.
+async fn may_use_heavy_commands(ctx: &Context, caller: &Caller, settings: &GuildSettings) -> bool {
+    let Some(guild_id) = caller.guild_id.filter(|_| settings.limits_roles()) else {
+        return true;
+    };
+    let roles = match &caller.roles {
+        Some(roles) => roles.clone(),
+        None => match guild_id.member(&ctx.http, caller.user_id).await {
+            Ok(member) => member.roles,
+            Err(why) => {
+                println!("Error fetching member {}: {:?}", caller.user_id, why);
+                return false;
+            }
+        },
+    };
+    settings.allows_roles(&roles) || is_guild_admin(ctx, caller).await
+}
+
+// Why a caller may not use a command.
+enum Denied {
+    // The server doesn't answer commands in this channel.
+    Channel,
+    AdminOnly,
+    // The server limits the command to roles the caller doesn't have.
+    Roles,
+    // A cooldown or the cap on running commands.
+    Limited(Limited),
+}
+
+impl Denied {
+    fn message(&self, spec: &CommandSpec, prefix: &str, locale: Locale) -> String {
+        match self {
+            Denied::Channel => locale.t("channel-denied"),
+            Denied::AdminOnly => locale.t_args("admin-only", &[("usage", commands::usage(spec, prefix).into())]),
+            Denied::Roles => locale.t_args("perms-role-denied", &[("command", format!("{}{}", prefix, spec.name).into())]),
+            Denied::Limited(limited) => {
+                let command = format!("{}{}", prefix, spec.name);
+                match limited {
+                    Limited::Cooldown(Scope::User, wait) => locale.t_args("cooldown", &[("command", command.into()), ("seconds", rate_limit::seconds_left(*wait).into())]),
+                    Limited::Cooldown(Scope::Guild, wait) => locale.t_args("cooldown-guild", &[("command", command.into()), ("seconds", rate_limit::seconds_left(*wait).into())]),
+                    Limited::Busy => locale.t("busy"),
+                }
+            },
+        }
+    }
+}
+
+// The channel, role and anti-spam checks for commands and for the buttons and menus they post,
+// run before anything is fetched from Jikan. The returned guard counts towards the caller's
+// running commands until it's dropped.
+async fn check_access(ctx: &Context, caller: &Caller, spec: &'static CommandSpec, settings: &GuildSettings) -> Result<rate_limit::Running, Denied> {
+    // `!config` and `!perms` stay usable everywhere so admins can't lock themselves out.
+    let is_config = matches!(spec.id, CommandId::ConfigShow | CommandId::ConfigSet | CommandId::PermsShow | CommandId::PermsAllow | CommandId::PermsDeny);
+    if !is_config && !settings.allows_channel(caller.channel_id) {
+        return Err(Denied::Channel);
+    }
+    if spec.permission == Permission::GuildAdmin && !is_guild_admin(ctx, caller).await {
+        return Err(Denied::AdminOnly);
+    }
+    if spec.permission == Permission::Heavy && !may_use_heavy_commands(ctx, caller, settings).await {
+        return Err(Denied::Roles);
+    }
+    rate_limit::start(caller.user_id.get(), caller.guild_id.map(|id| id.get()), spec.name, settings.cooldowns_for(spec)).map_err(Denied::Limited)
+}
+
+fn parse_mal_ids(args: &[&str]) -> Option<Vec<u32>> {
+    args.iter().map(|arg| arg.parse::<u32>().ok()).collect()
+}
+
+// `subcommand` is `subscribe`, `unsubscribe` or `list`; admin rights are checked by the dispatcher.
+async fn handle_news_command(ctx: &Context, caller: &Caller, subcommand: &str, args: &str, prefix: &str, locale: Locale) {
+    let Some(guild_id) = caller.guild_id else {
+        if let Err(why) = caller.reply(ctx, locale.t("news-guild-only")).await {
+            println!("Error sending message: {:?}", why);
+        }
+        return;
+    };
+
+    let parts: Vec<&str> = std::iter::once(subcommand).chain(args.split_whitespace()).collect();
+    let reply = match parts.split_first() {
+        Some((&"subscribe", ids)) if !ids.is_empty() => match parse_mal_ids(ids) {
+            Some(mal_ids) => {
+                let added = news_feed::subscribe(guild_id.to_string(), caller.channel_id.to_string(), &mal_ids).await;
+                if added.is_empty() {
+                    locale.t("news-already-subscribed")
+                } else {
+                    let ids: Vec<String> = added.iter().map(|id| id.to_string()).collect();
+                    locale.t_args("news-subscribed", &[("ids", ids.join(", ").into())])
+                }
+            },
+            None => locale.t("news-invalid-id"),
+        },
+        Some((&"unsubscribe", [all])) if all.eq_ignore_ascii_case("all") => {
+            let removed = news_feed::unsubscribe(&caller.channel_id.to_string(), None).await;
+            locale.t_args("news-removed", &[("count", removed.into())])
+        },
+        Some((&"unsubscribe", ids)) if !ids.is_empty() => match parse_mal_ids(ids) {
+            Some(mal_ids) => {
+                let removed = news_feed::unsubscribe(&caller.channel_id.to_string(), Some(&mal_ids)).await;
+                locale.t_args("news-removed", &[("count", removed.into())])
+            },
+            None => locale.t("news-invalid-id"),
+        },
+        Some((&"list", [])) => {
+            let subscriptions = news_feed::guild_subscriptions(&guild_id.to_string()).await;
+            if subscriptions.is_empty() {
+                locale.t("news-list-empty")
+            } else {
+                let lines: Vec<String> = subscriptions.iter().map(|(channel_id, mal_ids)| {
+                    let ids: Vec<String> = mal_ids.iter().map(|id| id.to_string()).collect();
+                    format!("<#{}>: {}", channel_id, ids.join(", "))
+                }).collect();
+                format!("**{}**\n{}", locale.t("news-list-title"), lines.join("\n"))
+            }
+        },
+        _ => locale.t_args("news-usage", &[("prefix", prefix.into())]),
+    };
+
+    if let Err(why) = caller.reply(ctx, reply).await {
+        println!("Error sending message: {:?}", why);
+    }
+}
+
+const PICTURES_BUTTON_PREFIX: &str = "pictures:";
+// How many promos and music videos `!anime videos` lists each.
+const VIDEOS_LIST_LIMIT: usize = 10;
+
+// One picture of the gallery; `number` starts at 1.
+async fn picture_page(mal_id: u32, number: u32, locale: Locale) -> Result<(CreateEmbed, Vec<CreateActionRow>), String> {
+    let pictures = jikan_api::get_anime_pictures(mal_id).await
+        .ok_or_else(|| locale.t_args("pictures-error", &[("id", mal_id.into())]))?;
+    let picture_url = pictures.get(number as usize - 1)
+        .and_then(|p| p.largest_url())
+        .ok_or_else(|| locale.t_args("pictures-none", &[("id", mal_id.into())]))?;
+
+    let embed = CreateEmbed::new()
+        .title(locale.t_args("pictures-title", &[("id", mal_id.into())]))
+        .url(picture_url)
+        .image(picture_url)
+        .footer(CreateEmbedFooter::new(locale.t_args("pictures-footer", &[("number", number.into()), ("total", pictures.len().into())])))
+        .timestamp(Timestamp::now());
+
+    let base_id = format!("{}{}:", PICTURES_BUTTON_PREFIX, mal_id);
+    Ok((embed, page_buttons(&base_id, number, (number as usize) < pictures.len(), locale)))
+}
+
+async fn handle_anime_pictures_command(ctx: &Context, caller: &Caller, mal_id: u32, locale: Locale) {
+    match picture_page(mal_id, 1, locale).await {
+        Ok((embed, components)) => send_embed_with_components(ctx, caller, embed, components).await,
+        Err(error) => {
+            if let Err(why) = caller.say(ctx, error).await {
+                println!("Error sending message: {:?}", why);
+            }
+        }
+    }
+}
+
+async fn handle_pictures_page_button(ctx: &Context, component: &ComponentInteraction, args: &str) {
+    let Some((mal_id, number)) = args.split_once(':')
+        .and_then(|(id, number)| Some((id.parse::<u32>().ok()?, number.parse::<u32>().ok()?)))
+        .filter(|(_, number)| *number >= 1) else {
+        return;
+    };
+
+    if let Err(why) = component.defer(&ctx.http).await {
+        println!("Error acknowledging pictures page: {:?}", why);
+        return;
+    }
+
+    let settings = guild_settings::get(component.guild_id).await;
+    let locale = locale_for(component.user.id, &settings).await;
+    let edit = match picture_page(mal_id, number, locale).await {
+        Ok((embed, components)) => EditInteractionResponse::new().embed(embed::fit(settings.style(embed))).components(components),
+        Err(error) => EditInteractionResponse::new().content(error),
+    };
+    if let Err(why) = component.edit_response(&ctx.http, edit).await {
+        println!("Error editing pictures message: {:?}", why);
+    }
+}
+
+async fn handle_anime_videos_command(ctx: &Context, caller: &Caller, mal_id: u32, locale: Locale) {
+    let videos = match jikan_api::get_anime_videos(mal_id).await {
+        Some(videos) => videos,
+        None => {
+            if let Err(why) = caller.say(ctx, locale.t_args("videos-error", &[("id", mal_id.into())])).await {
+                println!("Error sending message: {:?}", why);
+            }
+            return;
+        }
+    };
+    if videos.promo.is_empty() && videos.music_videos.is_empty() {
+        if let Err(why) = caller.say(ctx, locale.t_args("videos-none", &[("id", mal_id.into())])).await {
+            println!("Error sending message: {:?}", why);
+        }
+        return;
+    }
+
+    let mut embed = CreateEmbed::new()
+        .title(locale.t_args("videos-title", &[("id", mal_id.into())]))
+        .timestamp(Timestamp::now());
+
+    let promo_lines: Vec<String> = videos.promo.iter().take(VIDEOS_LIST_LIMIT)
+        .filter_map(|promo| Some(format!("[{}]({})", promo.title, promo.trailer.watch_url()?)))
+        .collect();
+    if !promo_lines.is_empty() {
+        embed = embed.field(locale.t("field-promos"), join_lines_limited(&promo_lines, 1024, locale), false);
+    }
+
+    let music_lines: Vec<String> = videos.music_videos.iter().take(VIDEOS_LIST_LIMIT)
+        .filter_map(|music_video| {
+            let mut line = format!("[{}]({})", music_video.title, music_video.video.watch_url()?);
+            if let Some(meta) = &music_video.meta {
+                match (&meta.title, &meta.author) {
+                    (Some(title), Some(author)) => {
+                        let song = locale.t_args("videos-song-by", &[("title", title.as_str().into()), ("author", author.as_str().into())]);
+                        line.push_str(&format!(" - {}", song));
+                    },
+                    (Some(title), None) => line.push_str(&format!(" - {}", title)),
+                    _ => {},
+                }
+            }
+            Some(line)
+        })
+        .collect();
+    if !music_lines.is_empty() {
+        embed = embed.field(locale.t("field-music-videos"), join_lines_limited(&music_lines, 1024, locale), false);
+    }
+
+    let thumbnail = videos.promo.iter().map(|promo| &promo.trailer)
+        .chain(videos.music_videos.iter().map(|music_video| &music_video.video))
+        .find_map(|video| video.thumbnail_url());
+    if let Some(thumbnail_url) = thumbnail {
+        embed = embed.image(thumbnail_url);
+    }
+
+    send_embed_message(ctx, caller, embed).await;
+}
+
+// Where `!anime themes` links each song; the song title and artist are appended as the search query.
+fn song_search_links(song: &ThemeSong) -> String {
+    let query = match &song.artist {
+        Some(artist) => format!("{} {}", song.title, artist),
+        None => song.title.clone(),
+    };
+    let youtube = reqwest::Url::parse_with_params("https://www.youtube.com/results", &[("search_query", query.as_str())]);
+    let spotify = reqwest::Url::parse("https://open.spotify.com/search").map(|mut url| {
+        if let Ok(mut segments) = url.path_segments_mut() {
+            segments.push(&query);
+        }
+        url
+    });
+
+    let mut links = Vec::new();
+    if let Ok(url) = youtube {
+        links.push(format!("[YouTube]({})", url));
+    }
+    if let Ok(url) = spotify {
+        links.push(format!("[Spotify]({})", url));
+    }
+    links.join(" | ")
+}
+
+fn theme_song_line(kind: &str, index: usize, text: &str, locale: Locale) -> String {
+    let song = ThemeSong::parse(text);
+    let number = song.number.unwrap_or(index as u32 + 1);
+    let mut line = format!("**{}{}** \"{}\"", kind, number, song.title);
+    if let Some(artist) = &song.artist {
+        line.push(' ');
+        line.push_str(&locale.t_args("themes-song-by", &[("artist", artist.as_str().into())]));
+    }
+    if let Some(episodes) = &song.episodes {
+        line.push_str(&format!(" ({})", episodes));
+    }
+    line.push_str(&format!("\n{}", song_search_links(&song)));
+    line
+}
+
+// Packs lines into as few embed field values (max 1024 characters each) as possible.
+fn chunk_lines(lines: &[String], max_len: usize) -> Vec<String> {
+    let mut chunks: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for line in lines {
+        let line = embed::truncate(line, max_len);
+        if !current.is_empty() && current.len() + line.len() + 1 > max_len {
+            chunks.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current.push_str(&line);
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+async fn handle_anime_themes_command(ctx: &Context, caller: &Caller, mal_id: u32, locale: Locale) {
+    let themes = match jikan_api::get_anime_themes(mal_id).await {
+        Some(themes) => themes,
+        None => {
+            if let Err(why) = caller.say(ctx, locale.t_args("themes-error", &[("id", mal_id.into())])).await {
+                println!("Error sending message: {:?}", why);
+            }
+            return;
+        }
+    };
+    if themes.openings.is_empty() && themes.endings.is_empty() {
+        if let Err(why) = caller.say(ctx, locale.t_args("themes-none", &[("id", mal_id.into())])).await {
+            println!("Error sending message: {:?}", why);
+        }
+        return;
+    }
+
+    let title = jikan_api::get_anime_details_by_id(mal_id).await
+        .map_or_else(|| format!("MAL ID: {}", mal_id), |details| details.title);
+    let mut embed = CreateEmbed::new()
+        .title(locale.t_args("themes-title", &[("title", title.as_str().into())]))
+        .timestamp(Timestamp::now());
+
+    let mut fields: Vec<(String, String)> = Vec::new();
+    for (heading, kind, songs) in [("field-openings", "OP", &themes.openings), ("field-endings", "ED", &themes.endings)] {
+        let heading = locale.t(heading);
+        let lines: Vec<String> = songs.iter().enumerate().map(|(i, text)| theme_song_line(kind, i, text, locale)).collect();
+        for (i, chunk) in chunk_lines(&lines, 1024).into_iter().enumerate() {
+            let name = if i == 0 { heading.clone() } else { locale.t_args("field-continued", &[("field", heading.as_str().into())]) };
+            fields.push((name, chunk));
+        }
+    }
+    // Discord allows 25 fields and 6000 characters per embed; only the longest-running shows get cut off.
+    let mut total_len = title.len();
+    for (name, value) in fields.into_iter().take(25) {
+        total_len += name.len() + value.len();
+        if total_len > 5800 {
+            embed = embed.footer(CreateEmbedFooter::new(locale.t("themes-truncated")));
+            break;
+        }
+        embed = embed.field(name, value, false);
+    }
+
+    send_embed_message(ctx, caller, embed).await;
+}
+
+// How many favorites and recent updates each section of `!mal` lists.
+const PROFILE_LIST_LIMIT: usize = 5;
+
+// MAL usernames are 2 to 16 letters, digits, `_` or `-`; checking keeps odd input out of the request path.
+fn is_valid_mal_username(username: &str) -> bool {
+    (2..=16).contains(&username.len()) && username.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+fn date_part(timestamp: &str) -> &str {
+    timestamp.get(..10).unwrap_or(timestamp)
+}
+
+fn user_profile_embed(profile: &UserProfile, locale: Locale) -> CreateEmbed {
+    let mut embed = CreateEmbed::new()
+        .title(locale.t_args("profile-title", &[("user", profile.username.as_str().into())]))
+        .url(&profile.url)
+        .timestamp(Timestamp::now());
+
+    if let Some(avatar) = profile.images.as_ref().and_then(|i| i.largest_url()) {
+        embed = embed.thumbnail(avatar);
+    }
+
+    let mut about = Vec::new();
+    if let Some(joined) = &profile.joined {
+        about.push(locale.t_args("profile-joined", &[("date", date_part(joined).into())]));
+    }
+    if let Some(last_online) = &profile.last_online {
+        about.push(locale.t_args("profile-last-online", &[("date", date_part(last_online).into())]));
+    }
+    if let Some(location) = profile.location.as_ref().filter(|l| !l.is_empty()) {
+        about.push(locale.t_args("profile-location", &[("location", location.as_str().into())]));
+    }
+    if !about.is_empty() {
+        embed = embed.description(about.join(" - "));
+    }
+
+    if let Some(statistics) = &profile.statistics {
+        let anime = &statistics.anime;
+        embed = embed.field(locale.t("profile-anime-stats"), locale.t_args("profile-anime-stats-body", &[
+            ("days", format!("{:.1}", anime.days_watched).into()),
+            ("mean", format!("{:.2}", anime.mean_score).into()),
+            ("watching", anime.watching.into()),
+            ("completed", anime.completed.into()),
+            ("on_hold", anime.on_hold.into()),
+            ("dropped", anime.dropped.into()),
+            ("planned", anime.plan_to_watch.into()),
+            ("total", anime.total_entries.into()),
+            ("episodes", anime.episodes_watched.into()),
+        ]), true);
+        let manga = &statistics.manga;
+        embed = embed.field(locale.t("profile-manga-stats"), locale.t_args("profile-manga-stats-body", &[
+            ("days", format!("{:.1}", manga.days_read).into()),
+            ("mean", format!("{:.2}", manga.mean_score).into()),
+            ("reading", manga.reading.into()),
+            ("completed", manga.completed.into()),
+            ("on_hold", manga.on_hold.into()),
+            ("dropped", manga.dropped.into()),
+            ("planned", manga.plan_to_read.into()),
+            ("total", manga.total_entries.into()),
+            ("chapters", manga.chapters_read.into()),
+        ]), true);
+    }
+
+    let favorites = &profile.favorites;
+    let favorite_anime: Vec<String> = favorites.anime.iter().take(PROFILE_LIST_LIMIT).map(|f| format!("[{}]({})", f.title, f.url)).collect();
+    if !favorite_anime.is_empty() {
+        embed = embed.field(locale.t("profile-favorite-anime"), join_lines_limited(&favorite_anime, 1024, locale), false);
+    }
+    let favorite_manga: Vec<String> = favorites.manga.iter().take(PROFILE_LIST_LIMIT).map(|f| format!("[{}]({})", f.title, f.url)).collect();
+    if !favorite_manga.is_empty() {
+        embed = embed.field(locale.t("profile-favorite-manga"), join_lines_limited(&favorite_manga, 1024, locale), false);
+    }
+    let favorite_characters: Vec<String> = favorites.characters.iter().take(PROFILE_LIST_LIMIT).map(|f| format!("[{}]({})", f.name, f.url)).collect();
+    if !favorite_characters.is_empty() {
+        embed = embed.field(locale.t("profile-favorite-characters"), join_lines_limited(&favorite_characters, 1024, locale), false);
+    }
+
+    let anime_updates: Vec<String> = profile.updates.anime.iter().take(PROFILE_LIST_LIMIT).map(|update| {
+        let progress = match (update.episodes_seen, update.episodes_total) {
+            (Some(seen), Some(total)) => format!(" {}/{}", seen, total),
+            (Some(seen), None) => format!(" {}/?", seen),
+            _ => String::new(),
+        };
+        let score = update.score.filter(|s| *s > 0).map_or(String::new(), |s| format!(", {}", locale.t_args("profile-update-scored", &[("score", s.into())])));
+        let status = update.status.clone().unwrap_or_else(|| locale.t("profile-update-default"));
+        format!("[{}]({}): {}{}{}", update.entry.title, update.entry.url, status, progress, score)
+    }).collect();
+    if !anime_updates.is_empty() {
+        embed = embed.field(locale.t("profile-anime-updates"), join_lines_limited(&anime_updates, 1024, locale), false);
+    }
+    let manga_updates: Vec<String> = profile.updates.manga.iter().take(PROFILE_LIST_LIMIT).map(|update| {
+        let progress = match (update.chapters_read, update.chapters_total) {
+            (Some(read), Some(total)) => format!(" {}/{}", read, total),
+            (Some(read), None) => format!(" {}/?", read),
+            _ => String::new(),
+        };
+        let score = update.score.filter(|s| *s > 0).map_or(String::new(), |s| format!(", {}", locale.t_args("profile-update-scored", &[("score", s.into())])));
+        let status = update.status.clone().unwrap_or_else(|| locale.t("profile-update-default"));
+        format!("[{}]({}): {}{}{}", update.entry.title, update.entry.url, status, progress, score)
+    }).collect();
+    if !manga_updates.is_empty() {
+        embed = embed.field(locale.t("profile-manga-updates"), join_lines_limited(&manga_updates, 1024, locale), false);
+    }
+
+    embed
+}
+
+async fn handle_mal_profile_command(ctx: &Context, caller: &Caller, username: &str, locale: Locale) {
+    if !is_valid_mal_username(username) {
+        if let Err(why) = caller.reply(ctx, locale.t_args("profile-invalid-username", &[("user", username.into())])).await {
+            println!("Error sending message: {:?}", why);
+        }
+        return;
+    }
+
+    match jikan_api::get_user_profile(username).await {
+        Some(profile) => send_embed_message(ctx, caller, user_profile_embed(&profile, locale)).await,
+        None => {
+            if let Err(why) = caller.say(ctx, locale.t_args("profile-not-found", &[("user", username.into())])).await {
+                println!("Error sending message: {:?}", why);
+            }
+        }
+    }
+}
+
+// How many favorites each side of `!mal compare` lists.
+const COMPARE_LIST_LIMIT: usize = 5;
+
+fn taste_profile(profile: &UserProfile) -> TasteProfile {
+    let anime = profile.statistics.as_ref().map(|s| &s.anime);
+    TasteProfile {
+        favorite_anime: profile.favorites.anime.iter().map(|f| f.mal_id).collect(),
+        favorite_manga: profile.favorites.manga.iter().map(|f| f.mal_id).collect(),
+        favorite_characters: profile.favorites.characters.iter().map(|f| f.mal_id).collect(),
+        anime_mean_score: anime.map(|a| a.mean_score as f64).filter(|mean| *mean > 0.0),
+        anime_status_counts: anime.map_or([0; 5], |a| [a.watching, a.completed, a.on_hold, a.dropped, a.plan_to_watch]),
+    }
+}
+
+fn compatibility_bar(score: u8) -> String {
+    let filled = (score as usize).div_ceil(10);
+    format!("{}{} **{}%**", "🟩".repeat(filled), "⬛".repeat(10 - filled), score)
+}
+
+// Renders one favorites category of the comparison under the `shared_key` heading. `lookup` turns an
+// ID back into a markdown link using whichever profile listed it.
+fn overlap_field(embed: CreateEmbed, shared_key: &str, overlap: &Overlap, names: [&str; 2], locale: Locale, lookup: impl Fn(u32) -> Option<String>) -> CreateEmbed {
+    let list = |ids: &[u32]| -> String {
+        let mut lines: Vec<String> = ids.iter().take(COMPARE_LIST_LIMIT).filter_map(|id| lookup(*id)).collect();
+        if ids.len() > COMPARE_LIST_LIMIT {
+            lines.push(locale.t_args("and-more", &[("count", (ids.len() - COMPARE_LIST_LIMIT).into())]));
+        }
+        if lines.is_empty() { locale.t("list-none") } else { join_lines_limited(&lines, 1024, locale) }
+    };
+
+    if overlap.shared.is_empty() && overlap.only_first.is_empty() && overlap.only_second.is_empty() {
+        return embed;
+    }
+    embed
+        .field(locale.t(shared_key), list(&overlap.shared), false)
+        .field(locale.t_args("compare-only", &[("user", names[0].into())]), list(&overlap.only_first), true)
+        .field(locale.t_args("compare-only", &[("user", names[1].into())]), list(&overlap.only_second), true)
+}
+
+fn user_compare_embed(first: &UserProfile, second: &UserProfile, locale: Locale) -> CreateEmbed {
+    let result = taste::compatibility(&taste_profile(first), &taste_profile(second));
+
+    let description = match result.score {
+        Some(score) => locale.t_args("compare-compatibility", &[("bar", compatibility_bar(score).into())]),
+        None => locale.t("compare-no-data"),
+    };
+    let mut embed = CreateEmbed::new()
+        .title(format!("{} vs {}", first.username, second.username))
+        .description(description)
+        .timestamp(Timestamp::now());
+
+    if let (Some(first_stats), Some(second_stats)) = (&first.statistics, &second.statistics) {
+        let stats = |anime: &jikan_api::UserAnimeStatistics| locale.t_args("compare-stats-body", &[
+            ("mean", format!("{:.2}", anime.mean_score).into()),
+            ("days", format!("{:.1}", anime.days_watched).into()),
+            ("completed", anime.completed.into()),
+            ("dropped", anime.dropped.into()),
+        ]);
+        embed = embed
+            .field(locale.t_args("compare-user-stats", &[("user", first.username.as_str().into())]), stats(&first_stats.anime), true)
+            .field(locale.t_args("compare-user-stats", &[("user", second.username.as_str().into())]), stats(&second_stats.anime), true);
+    }
+
+    let names = [first.username.as_str(), second.username.as_str()];
+    let entry_link = |entries: [&Vec<jikan_api::FavoriteEntry>; 2], id: u32| {
+        entries.iter().flat_map(|list| list.iter()).find(|f| f.mal_id == id).map(|f| format!("[{}]({})", f.title, f.url))
+    };
+    embed = overlap_field(embed, "compare-shared-anime", &result.anime, names, locale, |id| entry_link([&first.favorites.anime, &second.favorites.anime], id));
+    embed = overlap_field(embed, "compare-shared-manga", &result.manga, names, locale, |id| entry_link([&first.favorites.manga, &second.favorites.manga], id));
+    overlap_field(embed, "compare-shared-characters", &result.characters, names, locale, |id| {
+        first.favorites.characters.iter().chain(&second.favorites.characters).find(|f| f.mal_id == id).map(|f| format!("[{}]({})", f.name, f.url))
+    })
+}
+
+async fn handle_mal_compare_command(ctx: &Context, caller: &Caller, first: &str, second: &str, locale: Locale) {
+    let reply = if let Some(invalid) = [first, second].into_iter().find(|u| !is_valid_mal_username(u)) {
+        locale.t_args("profile-invalid-username", &[("user", invalid.into())])
+    } else if first.eq_ignore_ascii_case(second) {
+        locale.t("compare-same-user")
+    } else {
+        match (jikan_api::get_user_profile(first).await, jikan_api::get_user_profile(second).await) {
+            (Some(first_profile), Some(second_profile)) => {
+                send_embed_message(ctx, caller, user_compare_embed(&first_profile, &second_profile, locale)).await;
+                return;
+            },
+            (None, _) => locale.t_args("user-not-found", &[("user", first.into())]),
+            (_, None) => locale.t_args("user-not-found", &[("user", second.into())]),
+        }
+    };
+
+    if let Err(why) = caller.reply(ctx, reply).await {
+        println!("Error sending message: {:?}", why);
+    }
+}
+
+enum MalAction<'a> {
+    Profile(Option<&'a str>),
+    Compare(&'a str, Option<&'a str>),
+    Link(&'a str),
+    Unlink,
+}
+
+async fn handle_mal_command(ctx: &Context, caller: &Caller, action: MalAction<'_>, prefix: &str, locale: Locale) {
+    let user_id = caller.user_id.to_string();
+
+    let reply = match action {
+        MalAction::Profile(None) => {
+            let mal_links = load_mal_links().await;
+            match mal_links.links.get(&user_id) {
+                Some(username) => {
+                    handle_mal_profile_command(ctx, caller, username, locale).await;
                     return;
+                },
+                None => locale.t_args("mal-not-linked", &[("prefix", prefix.into())]),
+            }
+        },
+        MalAction::Profile(Some(username)) => {
+            handle_mal_profile_command(ctx, caller, username, locale).await;
+            return;
+        },
+        MalAction::Link(username) => {
+            if !is_valid_mal_username(username) {
+                locale.t_args("profile-invalid-username", &[("user", username.into())])
+            } else {
+                match jikan_api::get_user_profile(username).await {
+                    Some(profile) => {
+                        let mut mal_links = load_mal_links().await;
+                        mal_links.links.insert(user_id, profile.username.clone());
+                        save_mal_links(&mal_links).await;
+                        locale.t_args("mal-linked", &[("user", profile.username.into()), ("prefix", prefix.into())])
+                    },
+                    None => locale.t_args("user-not-found", &[("user", username.into())]),
                 }
-            };
-            handle_anime_details_command(&ctx, &msg, mal_id).await;
+            }
+        },
+        MalAction::Compare(first, Some(second)) => {
+            handle_mal_compare_command(ctx, caller, first, second, locale).await;
+            return;
+        },
+        MalAction::Compare(other, None) => {
+            let mal_links = load_mal_links().await;
+            match mal_links.links.get(&user_id) {
+                Some(own) => {
+                    handle_mal_compare_command(ctx, caller, own, other, locale).await;
+                    return;
+                },
+                None => locale.t_args("mal-compare-not-linked", &[("prefix", prefix.into())]),
+            }
+        },
+        MalAction::Unlink => {
+            let mut mal_links = load_mal_links().await;
+            if mal_links.links.remove(&user_id).is_some() {
+                save_mal_links(&mal_links).await;
+                locale.t("mal-unlinked")
+            } else {
+                locale.t("mal-no-link")
+            }
+        },
+    };
+
+    if let Err(why) = caller.reply(ctx, reply).await {
+        println!("Error sending message: {:?}", why);
+    }
+}
+
+// `!anime compare` takes two IDs, or two names separated by `vs` or `|`.
+fn split_compare_arguments(args: &str) -> Option<(&str, &str)> {
+    let separated = args.split_once(" vs ").or_else(|| args.split_once(" VS ")).or_else(|| args.split_once('|'));
+    let (first, second) = match separated {
+        Some(pair) => pair,
+        None => {
+            let mut words = args.split_whitespace();
+            match (words.next(), words.next(), words.next()) {
+                (Some(first), Some(second), None) => (first, second),
+                _ => return None,
+            }
+        }
+    };
+    let (first, second) = (first.trim(), second.trim());
+    if first.is_empty() || second.is_empty() {
+        return None;
+    }
+    Some((first, second))
+}
+
+// Which side wins a metric, if any. `lower_is_better` is for rankings like rank and popularity.
+fn metric_winner<T: PartialOrd>(first: Option<T>, second: Option<T>, lower_is_better: bool) -> [bool; 2] {
+    match (first, second) {
+        (Some(a), Some(b)) if a != b => {
+            let first_wins = (a > b) != lower_is_better;
+            [first_wins, !first_wins]
+        },
+        (Some(_), None) => [true, false],
+        (None, Some(_)) => [false, true],
+        _ => [false, false],
+    }
+}
+
+fn anime_compare_embed(first: &AnimeDetails, second: &AnimeDetails, locale: Locale) -> CreateEmbed {
+    let pair = [first, second];
+    let mut columns = [Vec::new(), Vec::new()];
+    let mut wins = [0; 2];
+
+    let mut ranked = |key: &str, values: [Option<String>; 2], winners: [bool; 2]| {
+        let label = locale.t(key);
+        for side in 0..2 {
+            let value = values[side].clone().unwrap_or_else(|| locale.t("not-available"));
+            if winners[side] {
+                wins[side] += 1;
+                columns[side].push(format!("**{}:** **{}** 🏆", label, value));
+            } else {
+                columns[side].push(format!("**{}:** {}", label, value));
+            }
+        }
+    };
+    ranked("field-score", pair.map(|d| d.score.map(|s| format!("{:.2}", s))), metric_winner(first.score, second.score, false));
+    ranked("field-rank", pair.map(|d| d.rank.map(|r| format!("#{}", r))), metric_winner(first.rank, second.rank, true));
+    ranked("field-popularity", pair.map(|d| d.popularity.map(|p| format!("#{}", p))), metric_winner(first.popularity, second.popularity, true));
+    ranked("field-members", pair.map(|d| d.members.map(|m| m.to_string())), metric_winner(first.members, second.members, false));
+
+    for (side, details) in pair.iter().enumerate() {
+        let or_na = |value: Option<String>| value.filter(|v| !v.is_empty()).unwrap_or_else(|| locale.t("not-available"));
+        let column = &mut columns[side];
+        column.push(format!("**{}:** {}", locale.t("field-episodes"), or_na(details.episodes.map(|e| e.to_string()))));
+        column.push(format!("**{}:** {}", locale.t("field-duration"), or_na(details.duration.clone())));
+        column.push(format!("**{}:** {}", locale.t("field-studios"), or_na(Some(join_names(&details.studios)))));
+        column.push(format!("**{}:** {}", locale.t("field-genres"), or_na(Some(embed::truncate(&join_names(&details.genres), 200)))));
+        column.push(format!("**{}:** {}", locale.t("field-source"), or_na(details.source.clone())));
+        column.push(format!("**{}:** {}", locale.t("field-aired"), or_na(details.aired.string.clone())));
+    }
+
+    let verdict = match wins[0].cmp(&wins[1]) {
+        std::cmp::Ordering::Greater => locale.t_args("anime-compare-wins", &[("title", first.title.as_str().into()), ("count", wins[0].into())]),
+        std::cmp::Ordering::Less => locale.t_args("anime-compare-wins", &[("title", second.title.as_str().into()), ("count", wins[1].into())]),
+        std::cmp::Ordering::Equal => locale.t("anime-compare-tie"),
+    };
+
+    let [first_column, second_column] = columns;
+    let mut embed = CreateEmbed::new()
+        .title(format!("{} vs {}", first.title, second.title))
+        .description(verdict)
+        .field(embed::truncate(&format!("{} (MAL ID: {})", first.title, first.mal_id), 250), join_lines_limited(&first_column, 1024, locale), true)
+        .field(embed::truncate(&format!("{} (MAL ID: {})", second.title, second.mal_id), 250), join_lines_limited(&second_column, 1024, locale), true)
+        .timestamp(Timestamp::now());
+    if let Some(poster) = first.images.as_ref().and_then(|i| i.largest_url()) {
+        embed = embed.thumbnail(poster);
+    }
+    embed
+}
+
+async fn handle_anime_compare_command(ctx: &Context, caller: &Caller, args: &str, prefix: &str, sfw: bool, locale: Locale) {
+    let Some((first_query, second_query)) = split_compare_arguments(args) else {
+        if let Err(why) = caller.reply(ctx, locale.t_args("anime-compare-usage", &[("prefix", prefix.into())])).await {
+            println!("Error sending message: {:?}", why);
+        }
+        return;
+    };
+
+    let mut resolved = Vec::new();
+    for query in [first_query, second_query] {
+        let problem = match resolve_anime(query, sfw).await {
+            AnimeLookup::Found(mal_id) => match jikan_api::get_anime_details_by_id(mal_id).await {
+                Some(details) => match adult_refusal(&details, sfw, locale) {
+                    Ok(()) => {
+                        resolved.push(details);
+                        continue;
+                    },
+                    Err(refusal) => refusal,
+                },
+                None => locale.t_args("details-not-found", &[("id", mal_id.into())]),
+            },
+            AnimeLookup::NotFound => locale.t_args("anime-not-found", &[("query", query.into())]),
+            AnimeLookup::Ambiguous(candidates) => {
+                let lines: Vec<String> = candidates.iter().map(|a| format!("• {} (MAL ID: {})", a.title, a.mal_id)).collect();
+                format!("{}\n{}", locale.t_args("anime-compare-ambiguous", &[("query", query.into())]), lines.join("\n"))
+            },
+        };
+        if let Err(why) = caller.reply(ctx, problem).await {
+            println!("Error sending message: {:?}", why);
+        }
+        return;
+    }
+
+    send_embed_message(ctx, caller, anime_compare_embed(&resolved[0], &resolved[1], locale)).await;
+}
+
+// `!help` buttons carry the page to show; page 1 is the category overview, then one page per category.
+const HELP_BUTTON_PREFIX: &str = "help:";
+
+fn help_page(page: u32, prefix: &str, locale: Locale) -> (CreateEmbed, bool) {
+    let total_pages = commands::CATEGORIES.len() as u32 + 1;
+    let page = page.clamp(1, total_pages);
+    let footer = locale.t_args("help-footer", &[("page", page.into()), ("total", total_pages.into()), ("prefix", prefix.into())]);
+    let mut embed = CreateEmbed::new()
+        .footer(CreateEmbedFooter::new(footer))
+        .timestamp(Timestamp::now());
+
+    // Page 1 is the overview, the others show one category each.
+    match (page as usize).checked_sub(2).and_then(|index| commands::CATEGORIES.get(index)) {
+        None => {
+            embed = embed
+                .title(locale.t("help-title"))
+                .description(locale.t("help-intro"));
+            for category in commands::CATEGORIES {
+                let names: Vec<String> = commands::in_category(category).map(|spec| format!("`{}{}`", prefix, spec.name)).collect();
+                embed = embed.field(locale.t(category.title_key()), names.join(", "), false);
+            }
+        },
+        Some(category) => {
+            let lines: Vec<String> = commands::in_category(*category).map(|spec| {
+                format!("• `{}`: {}", commands::usage(spec, prefix), locale.t(spec.help))
+            }).collect();
+            embed = embed
+                .title(locale.t_args("help-category-title", &[("category", locale.t(category.title_key()).into())]))
+                .description(join_lines_limited(&lines, 4096, locale));
+        },
+    }
+    (embed, page < total_pages)
+}
+
+fn command_help_embed(spec: &CommandSpec, settings: &GuildSettings, locale: Locale) -> CreateEmbed {
+    let prefix = settings.prefix.as_str();
+    let mut embed = CreateEmbed::new()
+        .title(format!("`{}`", commands::usage(spec, prefix)))
+        .description(locale.t(spec.help))
+        .timestamp(Timestamp::now());
+
+    if !spec.args.is_empty() {
+        let lines: Vec<String> = spec.args.iter().map(|arg| {
+            let optional = if arg.required { String::new() } else { format!(" {}", locale.t("help-optional")) };
+            format!("`{}`{}: {}", arg.name, optional, locale.t(arg.description))
+        }).collect();
+        embed = embed.field(locale.t("help-arguments"), lines.join("\n"), false);
+    }
+    let examples: Vec<String> = spec.examples.iter().map(|example| format!("`{}{}`", prefix, example)).collect();
+    embed = embed.field(locale.t("help-examples"), examples.join("\n"), false);
+    if !spec.aliases.is_empty() {
+        let aliases: Vec<String> = spec.aliases.iter().map(|alias| format!("`{}{}`", prefix, alias)).collect();
+        embed = embed.field(locale.t("help-aliases"), aliases.join(", "), true);
+    }
+    let (user_cooldown, guild_cooldown) = settings.cooldowns_for(spec);
+    if !guild_cooldown.is_zero() {
+        let cooldown = locale.t_args("help-cooldown-guild", &[("user", user_cooldown.as_secs().into()), ("guild", guild_cooldown.as_secs().into())]);
+        embed = embed.field(locale.t("help-cooldown"), cooldown, true);
+    } else if !user_cooldown.is_zero() {
+        embed = embed.field(locale.t("help-cooldown"), format!("{}s", user_cooldown.as_secs()), true);
+    }
+    match spec.permission {
+        Permission::GuildAdmin => embed = embed.field(locale.t("help-permission"), locale.t("help-admins-only"), true),
+        Permission::Heavy => embed = embed.field(locale.t("help-permission"), locale.t("help-heavy-command"), true),
+        Permission::Everyone => {},
+    }
+    embed
+}
+
+async fn send_help(ctx: &Context, caller: &Caller, command: Option<&str>, settings: &GuildSettings, locale: Locale) {
+    let prefix = settings.prefix.as_str();
+    let Some(command) = command else {
+        let (embed, has_next) = help_page(1, prefix, locale);
+        send_embed_with_components(ctx, caller, embed, page_buttons(HELP_BUTTON_PREFIX, 1, has_next, locale)).await;
+        return;
+    };
+
+    match commands::find_command(command) {
+        Some(spec) => send_embed_message(ctx, caller, command_help_embed(spec, settings, locale)).await,
+        None => {
+            if let Err(why) = caller.reply(ctx, locale.t_args("help-unknown-command", &[("command", command.into()), ("prefix", prefix.into())])).await {
+                println!("Error sending message: {:?}", why);
+            }
+        }
+    }
+}
+
+async fn handle_help_page_button(ctx: &Context, component: &ComponentInteraction, args: &str) {
+    let Ok(page) = args.parse::<u32>() else {
+        return;
+    };
+    let settings = guild_settings::get(component.guild_id).await;
+    let locale = locale_for(component.user.id, &settings).await;
+    let (embed, has_next) = help_page(page, &settings.prefix, locale);
+    let update = CreateInteractionResponseMessage::new().embed(embed::fit(settings.style(embed))).components(page_buttons(HELP_BUTTON_PREFIX, page, has_next, locale));
+    if let Err(why) = component.create_response(&ctx.http, CreateInteractionResponse::UpdateMessage(update)).await {
+        println!("Error editing help message: {:?}", why);
+    }
+}
+
+// The bot's own user ID, known once `ready` fires; mentioning the bot works as a prefix in every server.
+static BOT_ID: OnceLock<UserId> = OnceLock::new();
+
+// Removes the guild's prefix, or a mention of the bot, from the start of a message.
+fn strip_prefix<'a>(content: &'a str, prefix: &str) -> Option<&'a str> {
+    if let Some(bot_id) = BOT_ID.get() {
+        for mention in [format!("<@{}>", bot_id), format!("<@!{}>", bot_id)] {
+            if let Some(rest) = content.strip_prefix(&mention) {
+                return Some(rest);
+            }
         }
-        else if content.starts_with("!anime recommendations ") {
-            let parts: Vec<&str> = content.splitn(3, ' ').collect();
-            if parts.len() < 3 {
-                if let Err(why) = msg.reply(&ctx.http, "Please provide an anime ID for recommendations. Usage: `!anime recommendations <MAL_ID>`").await {
+    }
+    content.strip_prefix(prefix)
+}
+
+async fn handle_config_show_command(ctx: &Context, caller: &Caller, settings: &GuildSettings, locale: Locale) {
+    let mut embed = CreateEmbed::new()
+        .title(locale.t("config-title"))
+        .footer(CreateEmbedFooter::new(locale.t_args("config-footer", &[("prefix", settings.prefix.as_str().into())])))
+        .timestamp(Timestamp::now());
+    for key in guild_settings::KEYS {
+        embed = embed.field(key, settings.value(key), true);
+    }
+    send_embed_message(ctx, caller, embed).await;
+}
+
+// Language codes as shown to users, e.g. `en|tr`.
+fn language_codes(separator: &str) -> String {
+    LOCALES.map(|locale| locale.code()).join(separator)
+}
+
+async fn handle_config_set_command(ctx: &Context, caller: &Caller, key: &str, value: &str, locale: Locale) {
+    let Some(guild_id) = caller.guild_id else {
+        return;
+    };
+    let key = key.to_lowercase();
+    // The other permission lists are changed with `!perms`.
+    let result = match guild_settings::KEYS.contains(&key.as_str()) {
+        true => guild_settings::update(guild_id, &key, value).await,
+        false => Err("setting-error-unknown"),
+    };
+    let reply = match result {
+        Ok(settings) => locale.t_args("config-updated", &[("key", key.as_str().into()), ("value", settings.value(&key).into())]),
+        Err(error) => locale.t_args(error, &[
+            ("key", key.as_str().into()),
+            ("keys", guild_settings::KEYS.join(", ").into()),
+            ("languages", language_codes(", ").into()),
+            ("max", guild_settings::MAX_PREFIX_LEN.into()),
+        ]),
+    };
+    if let Err(why) = caller.reply(ctx, reply).await {
+        println!("Error sending message: {:?}", why);
+    }
+}
+
+async fn handle_perms_show_command(ctx: &Context, caller: &Caller, settings: &GuildSettings, locale: Locale) {
+    let heavy: Vec<String> = commands::COMMANDS.iter()
+        .filter(|spec| spec.permission == Permission::Heavy)
+        .map(|spec| format!("`{}{}`", settings.prefix, spec.name))
+        .collect();
+    let mut embed = CreateEmbed::new()
+        .title(locale.t("perms-title"))
+        .description(locale.t_args("perms-description", &[("commands", heavy.join(", ").into())]))
+        .footer(CreateEmbedFooter::new(locale.t_args("perms-footer", &[("prefix", settings.prefix.as_str().into())])))
+        .timestamp(Timestamp::now());
+    for key in guild_settings::PERMISSION_KEYS {
+        embed = embed.field(key, settings.value(key), true);
+    }
+    send_embed_message(ctx, caller, embed).await;
+}
+
+// `!perms allow|deny channels|roles <targets>` replaces one of the four lists.
+async fn handle_perms_set_command(ctx: &Context, caller: &Caller, allow: bool, list: &str, targets: &str, locale: Locale) {
+    let Some(guild_id) = caller.guild_id else {
+        return;
+    };
+    let list = match list.to_lowercase().as_str() {
+        "channel" | "channels" => "channels",
+        "role" | "roles" => "roles",
+        _ => {
+            if let Err(why) = caller.reply(ctx, locale.t_args("perms-error-list", &[("list", list.into())])).await {
+                println!("Error sending message: {:?}", why);
+            }
+            return;
+        },
+    };
+    let key = format!("{}_{}", if allow { "allowed" } else { "denied" }, list);
+    let reply = match guild_settings::update(guild_id, &key, targets).await {
+        Ok(settings) => locale.t_args("config-updated", &[("key", key.as_str().into()), ("value", settings.value(&key).into())]),
+        Err(error) => locale.t(error),
+    };
+    if let Err(why) = caller.reply(ctx, reply).await {
+        println!("Error sending message: {:?}", why);
+    }
+}
+
+// The language a user sees: their own choice from `!language`, else the server's.
+async fn locale_for(user_id: UserId, settings: &GuildSettings) -> Locale {
+    user_settings::get(user_id).await.language.unwrap_or(settings.language)
+}
+
+// `!language` shows the caller's language, `!language <code>` picks one and `!language default`
+// goes back to following the server.
+async fn handle_language_command(ctx: &Context, caller: &Caller, choice: Option<&str>, settings: &GuildSettings, locale: Locale) {
+    let reply = match choice {
+        None => locale.t_args("language-current", &[
+            ("language", locale.t("language-name").into()),
+            ("prefix", settings.prefix.as_str().into()),
+            ("languages", language_codes("|").into()),
+        ]),
+        Some(choice) if choice.eq_ignore_ascii_case("default") => {
+            user_settings::set_language(caller.user_id, None).await;
+            settings.language.t("language-reset")
+        },
+        Some(choice) => match Locale::from_code(choice) {
+            Some(chosen) => {
+                user_settings::set_language(caller.user_id, Some(chosen)).await;
+                chosen.t_args("language-set", &[("language", chosen.t("language-name").into())])
+            },
+            None => locale.t_args("language-invalid", &[("languages", language_codes(", ").into())]),
+        },
+    };
+    if let Err(why) = caller.reply(ctx, reply).await {
+        println!("Error sending message: {:?}", why);
+    }
+}
+
+// Parses `content` (with the prefix already removed) against the command registry, checks the
+// channel, permissions, cooldowns and running commands, then runs the command.
+async fn run_command(ctx: &Context, msg: &Message, content: &str, settings: &GuildSettings) {
+    let prefix = settings.prefix.as_str();
+    let invocation = match commands::parse(content) {
+        None => return,
+        Some(Ok(invocation)) => invocation,
+        Some(Err(error)) => {
+            if settings.allows_channel(msg.channel_id) {
+                let locale = locale_for(msg.author.id, settings).await;
+                if let Err(why) = msg.reply(&ctx.http, error.message(prefix, locale)).await {
                     println!("Error sending message: {:?}", why);
                 }
-                return;
             }
-            let mal_id_str = parts[2].trim();
-            let mal_id = match mal_id_str.parse::<u32>() {
-                Ok(id) => id,
-                Err(_) => {
-                    if let Err(why) = msg.reply(&ctx.http, "Invalid MAL ID. Please provide a numeric ID.").await {
-                        println!("Error sending message: {:?}", why);
-                    }
-                    return;
-                }
-            };
-            handle_anime_recommendations_command(&ctx, &msg, mal_id).await;
-        }
-        else if content == "!lain" {
-            let mal_id = 339; // MAL ID for Serial Experiments Lain
-            handle_anime_details_command(&ctx, &msg, mal_id).await;
+            return;
         }
-        else if content == "!lain recommendations" {
-            let mal_id = 339; // MAL ID for Serial Experiments Lain
-            handle_anime_recommendations_command(&ctx, &msg, mal_id).await;
+    };
+    let spec = invocation.spec;
+
+    let caller = Caller::of_message(msg);
+    // Held until the command finishes, so it counts towards the user's running commands.
+    let _running = match check_access(ctx, &caller, spec, settings).await {
+        Ok(running) => running,
+        // Messages in channels the bot doesn't answer in are ignored without a word.
+        Err(Denied::Channel) => return,
+        Err(Denied::Limited(_)) if !rate_limit::should_warn(msg.author.id.get()) => return,
+        Err(denied) => {
+            let locale = locale_for(msg.author.id, settings).await;
+            if let Err(why) = msg.reply(&ctx.http, denied.message(spec, prefix, locale)).await {
+                println!("Error sending message: {:?}", why);
+            }
+            return;
         }
-        else if content.starts_with("!anime ") {
-            let query = content.trim_start_matches("!anime ").trim();
-            if query.is_empty() {
-                if let Err(why) = msg.reply(&ctx.http, "Please provide an anime name to search. Usage: `!anime <anime name>` or `!anime details <MAL_ID>` or `!anime recommendations <MAL_ID>`").await {
+    };
+    let locale = locale_for(msg.author.id, settings).await;
+
+    // Shown until the command has answered, instead of a separate "Fetching..." message.
+    let _typing = msg.channel_id.start_typing(&ctx.http);
+    dispatch(ctx, &caller, &invocation, settings, locale).await;
+}
+
+// Runs a command that passed every check, for a prefix or a slash command.
+async fn dispatch(ctx: &Context, caller: &Caller, invocation: &Invocation, settings: &GuildSettings, locale: Locale) {
+    let prefix = settings.prefix.as_str();
+    let spec = invocation.spec;
+
+    // Outside channels where the content policy allows adult entries, searches ask Jikan to leave
+    // them out and commands about a single entry refuse to show one.
+    let sfw = !allows_adult_content(ctx, caller.channel_id, settings).await;
+    if let Some(mal_id) = invocation.number("MAL_ID").filter(|_| sfw) {
+        if let Some(details) = jikan_api::get_anime_details_by_id(mal_id).await {
+            if let Err(refusal) = adult_refusal(&details, sfw, locale) {
+                if let Err(why) = caller.reply(ctx, refusal).await {
                     println!("Error sending message: {:?}", why);
                 }
                 return;
             }
-            handle_anime_search_command(&ctx, &msg, query, user_id).await;
+        }
+    }
+
+    // Required arguments are always present once parsing succeeded.
+    let text = |name: &str| invocation.text(name).unwrap_or_default();
+    let number = |name: &str| invocation.number(name).unwrap_or_default();
+    match spec.id {
+        CommandId::Help => send_help(ctx, caller, invocation.text("command"), settings, locale).await,
+        CommandId::Language => handle_language_command(ctx, caller, invocation.text("language"), settings, locale).await,
+        CommandId::AnimeSearch => handle_anime_search_command(ctx, caller, text("anime name"), caller.user_id.to_string(), prefix, sfw, locale).await,
+        CommandId::AnimeDetails => handle_anime_details_command(ctx, caller, text("MAL_ID or name"), sfw, locale).await,
+        CommandId::AnimeRecommendations => handle_anime_recommendations_command(ctx, caller, text("MAL_ID or name"), sfw, locale).await,
+        CommandId::AnimeRelated => handle_anime_related_command(ctx, caller, number("MAL_ID"), prefix, sfw, locale).await,
+        CommandId::AnimeWatchOrder => handle_anime_watch_order_command(ctx, caller, number("MAL_ID"), sfw, locale).await,
+        CommandId::AnimeEpisodes => handle_anime_episodes_command(ctx, caller, number("MAL_ID"), invocation.number("page").unwrap_or(1), prefix, locale).await,
+        CommandId::AnimeEpisode => handle_anime_episode_command(ctx, caller, number("MAL_ID"), number("episode"), locale).await,
+        CommandId::AnimeWhere => handle_anime_where_command(ctx, caller, text("MAL_ID or name"), sfw, locale).await,
+        CommandId::AnimeStats => handle_anime_stats_command(ctx, caller, number("MAL_ID"), locale).await,
+        CommandId::AnimeReviews => handle_anime_reviews_command(ctx, caller, number("MAL_ID"), locale).await,
+        CommandId::AnimeNews => handle_anime_news_command(ctx, caller, number("MAL_ID"), prefix, locale).await,
+        CommandId::AnimePictures => handle_anime_pictures_command(ctx, caller, number("MAL_ID"), locale).await,
+        CommandId::AnimeVideos => handle_anime_videos_command(ctx, caller, number("MAL_ID"), locale).await,
+        CommandId::AnimeThemes => handle_anime_themes_command(ctx, caller, number("MAL_ID"), locale).await,
+        CommandId::AnimeCompare => handle_anime_compare_command(ctx, caller, text("anime vs anime"), prefix, sfw, locale).await,
+        CommandId::NewsSubscribe => handle_news_command(ctx, caller, "subscribe", text("MAL_IDs"), prefix, locale).await,
+        CommandId::NewsUnsubscribe => handle_news_command(ctx, caller, "unsubscribe", text("MAL_IDs or all"), prefix, locale).await,
+        CommandId::NewsList => handle_news_command(ctx, caller, "list", "", prefix, locale).await,
+        CommandId::Random => handle_random_command(ctx, caller, text("filters"), prefix, sfw, locale).await,
+        CommandId::Genres => handle_genres_command(ctx, caller, prefix, locale).await,
+        CommandId::Genre => handle_genre_command(ctx, caller, text("name"), prefix, sfw, locale).await,
+        CommandId::Studio => handle_studio_command(ctx, caller, text("name"), sfw, locale).await,
+        CommandId::MalProfile => handle_mal_command(ctx, caller, MalAction::Profile(invocation.text("username")), prefix, locale).await,
+        CommandId::MalCompare => handle_mal_command(ctx, caller, MalAction::Compare(text("user1"), invocation.text("user2")), prefix, locale).await,
+        CommandId::MalLink => handle_mal_command(ctx, caller, MalAction::Link(text("username")), prefix, locale).await,
+        CommandId::MalUnlink => handle_mal_command(ctx, caller, MalAction::Unlink, prefix, locale).await,
+        CommandId::ConfigShow => handle_config_show_command(ctx, caller, settings, locale).await,
+        CommandId::ConfigSet => handle_config_set_command(ctx, caller, text("key"), text("value"), locale).await,
+        CommandId::PermsShow => handle_perms_show_command(ctx, caller, settings, locale).await,
+        CommandId::PermsAllow => handle_perms_set_command(ctx, caller, true, text("channels or roles"), text("targets"), locale).await,
+        CommandId::PermsDeny => handle_perms_set_command(ctx, caller, false, text("channels or roles"), text("targets"), locale).await,
+        // MAL ID for Serial Experiments Lain
+        CommandId::Lain => handle_anime_details_command(ctx, caller, "339", sfw, locale).await,
+        CommandId::LainRecommendations => handle_anime_recommendations_command(ctx, caller, "339", sfw, locale).await,
+    }
+}
+
+
+// The command that posts each kind of button or menu. Using one counts as using that command.
+fn component_command(custom_id: &str) -> Option<CommandId> {
+    let routes = [
+        (RANDOM_BUTTON_PREFIX, CommandId::Random),
+        (STUDIO_BUTTON_PREFIX, CommandId::Studio),
+        (EPISODES_BUTTON_PREFIX, CommandId::AnimeEpisodes),
+        (PICTURES_BUTTON_PREFIX, CommandId::AnimePictures),
+        (REVIEWS_BUTTON_PREFIX, CommandId::AnimeReviews),
+        (REVIEW_FULL_BUTTON_PREFIX, CommandId::AnimeReviews),
+        (HELP_BUTTON_PREFIX, CommandId::Help),
+    ];
+    match custom_id.strip_prefix(PICK_MENU_PREFIX) {
+        Some(args) => match parse_pick_menu(args)? {
+            (_, "details") => Some(CommandId::AnimeDetails),
+            (_, "recommendations") => Some(CommandId::AnimeRecommendations),
+            _ => None,
+        },
+        None => routes.iter().find(|(prefix, _)| custom_id.starts_with(prefix)).map(|(_, id)| *id),
+    }
+}
+
+// Splits the arguments of a select menu custom ID into the user who may pick and the command.
+fn parse_pick_menu(args: &str) -> Option<(UserId, &str)> {
+    let (user_id, command) = args.split_once(':')?;
+    let user_id = user_id.parse::<u64>().ok().filter(|id| *id != 0)?;
+    Some((UserId::new(user_id), command))
+}
+
+// An answer only the user who clicked sees.
+fn ephemeral(content: String) -> CreateInteractionResponse {
+    CreateInteractionResponse::Message(CreateInteractionResponseMessage::new().content(content).ephemeral(true))
+}
+
+// Buttons and menus go through the same checks as the command that posted them before their
+// handler fetches anything.
+async fn handle_component(ctx: &Context, component: &ComponentInteraction) {
+    let custom_id = component.data.custom_id.as_str();
+    let Some(command_id) = component_command(custom_id) else {
+        return;
+    };
+    let spec = commands::spec(command_id);
+    let settings = guild_settings::get(component.guild_id).await;
+
+    // Only the user who ran the command can pick from its menu.
+    let owner = custom_id.strip_prefix(PICK_MENU_PREFIX).and_then(parse_pick_menu).map(|(owner, _)| owner);
+    if owner.is_some_and(|owner| owner != component.user.id) {
+        let locale = locale_for(component.user.id, &settings).await;
+        if let Err(why) = component.create_response(&ctx.http, ephemeral(locale.t("anime-pick-not-yours"))).await {
+            println!("Error responding to component: {:?}", why);
+        }
+        return;
+    }
+
+    let _running = match check_access(ctx, &Caller::of_component(component), spec, &settings).await {
+        Ok(running) => running,
+        Err(denied) => {
+            // Every click needs an answer; once the user has been warned, it's a silent one.
+            let response = match denied {
+                Denied::Limited(_) if !rate_limit::should_warn(component.user.id.get()) => CreateInteractionResponse::Acknowledge,
+                denied => ephemeral(denied.message(spec, &settings.prefix, locale_for(component.user.id, &settings).await)),
+            };
+            if let Err(why) = component.create_response(&ctx.http, response).await {
+                println!("Error responding to component: {:?}", why);
+            }
+            return;
+        }
+    };
+
+    if let Some(args) = custom_id.strip_prefix(RANDOM_BUTTON_PREFIX) {
+        handle_random_reroll(ctx, component, args).await;
+    } else if let Some(args) = custom_id.strip_prefix(STUDIO_BUTTON_PREFIX) {
+        handle_studio_page_button(ctx, component, args).await;
+    } else if let Some(args) = custom_id.strip_prefix(EPISODES_BUTTON_PREFIX) {
+        handle_episodes_page_button(ctx, component, args).await;
+    } else if let Some(args) = custom_id.strip_prefix(PICTURES_BUTTON_PREFIX) {
+        handle_pictures_page_button(ctx, component, args).await;
+    } else if let Some(args) = custom_id.strip_prefix(REVIEWS_BUTTON_PREFIX) {
+        handle_reviews_page_button(ctx, component, args).await;
+    } else if let Some(args) = custom_id.strip_prefix(REVIEW_FULL_BUTTON_PREFIX) {
+        handle_review_full_button(ctx, component, args).await;
+    } else if let Some(args) = custom_id.strip_prefix(HELP_BUTTON_PREFIX) {
+        handle_help_page_button(ctx, component, args).await;
+    } else if let Some((_, command)) = custom_id.strip_prefix(PICK_MENU_PREFIX).and_then(parse_pick_menu) {
+        handle_pick_menu(ctx, component, command).await;
+    }
+}
+
+// Slash commands go through the same registry, checks and handlers as prefix commands. Refusals
+// only go to the user; otherwise the command is deferred and its handler fills in the response.
+async fn handle_slash_command(ctx: &Context, command: &CommandInteraction) {
+    let options = &command.data.options;
+    let text = commands::slash_to_text(&command.data.name, |name| {
+        options.iter().find(|option| option.name == name).and_then(|option| match &option.value {
+            CommandDataOptionValue::Integer(number) => Some(number.to_string()),
+            CommandDataOptionValue::String(text) => Some(text.clone()),
+            _ => None,
+        })
+    });
+    let Some(invocation) = text.as_deref().and_then(commands::parse) else {
+        return;
+    };
+
+    let settings = guild_settings::get(command.guild_id).await;
+    let locale = locale_for(command.user.id, &settings).await;
+    let caller = Caller::of_command(command);
+    let invocation = match invocation {
+        Ok(invocation) => invocation,
+        Err(error) => {
+            if let Err(why) = command.create_response(&ctx.http, ephemeral(error.message(&settings.prefix, locale))).await {
+                println!("Error responding to slash command: {:?}", why);
+            }
+            return;
+        }
+    };
+    // Unlike a message, a slash command always needs an answer, so warnings aren't rate-limited.
+    let _running = match check_access(ctx, &caller, invocation.spec, &settings).await {
+        Ok(running) => running,
+        Err(denied) => {
+            if let Err(why) = command.create_response(&ctx.http, ephemeral(denied.message(invocation.spec, &settings.prefix, locale))).await {
+                println!("Error responding to slash command: {:?}", why);
+            }
+            return;
+        }
+    };
+
+    if let Err(why) = command.defer(&ctx.http).await {
+        println!("Error responding to slash command: {:?}", why);
+        return;
+    }
+    dispatch(ctx, &caller, &invocation, &settings, locale).await;
+    caller.finish(ctx).await;
+}
+
+#[async_trait]
+impl EventHandler for Handler {
+    async fn message(&self, ctx: Context, msg: Message) {
+        if msg.author.bot {
+            return;
+        }
+
+        let settings = guild_settings::get(msg.guild_id).await;
+        if let Some(content) = strip_prefix(msg.content.trim(), &settings.prefix) {
+            run_command(&ctx, &msg, content, &settings).await;
+        }
+    }
+
+    async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
+        if let Interaction::Command(command) = &interaction {
+            handle_slash_command(&ctx, command).await;
+        } else if let Interaction::Component(component) = &interaction {
+            handle_component(&ctx, component).await;
         }
     }
 
@@ -352,7 +3147,83 @@ impl EventHandler for Handler {
         println!("Presence Update");
     }
 
-    async fn ready(&self, _: Context, ready: Ready) {
+    async fn ready(&self, ctx: Context, ready: Ready) {
         println!("{} is connected!", ready.user.name);
+        let _ = BOT_ID.set(ready.user.id);
+        if let Err(why) = Command::set_global_commands(&ctx.http, commands::slash_commands()).await {
+            println!("Error registering slash commands: {:?}", why);
+        }
+        news_feed::start_poller(ctx.http.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn title(embed: &CreateEmbed) -> String {
+        let json = serde_json::to_value(embed).expect("embeds serialize");
+        json["title"].as_str().unwrap_or_default().to_string()
+    }
+
+    #[test]
+    fn help_pages_cover_the_overview_and_every_category() {
+        let total_pages = commands::CATEGORIES.len() as u32 + 1;
+        let (overview, has_next) = help_page(1, "!", Locale::En);
+        assert_eq!(title(&overview), Locale::En.t("help-title"));
+        assert!(has_next);
+
+        let (last, has_next) = help_page(total_pages, "!", Locale::En);
+        let category = commands::CATEGORIES[commands::CATEGORIES.len() - 1];
+        assert_eq!(title(&last), Locale::En.t_args("help-category-title", &[("category", Locale::En.t(category.title_key()).into())]));
+        assert!(!has_next);
+
+        // Out of range pages from stale buttons land on the nearest page.
+        assert_eq!(title(&help_page(0, "!", Locale::En).0), title(&overview));
+        assert_eq!(title(&help_page(total_pages + 5, "!", Locale::En).0), title(&last));
+    }
+
+    #[test]
+    fn buttons_and_menus_belong_to_the_command_that_posts_them() {
+        let posted = [
+            (format!("{}manga sfw", RANDOM_BUTTON_PREFIX), CommandId::Random),
+            (format!("{}18:2", STUDIO_BUTTON_PREFIX), CommandId::Studio),
+            (format!("{}339:2", EPISODES_BUTTON_PREFIX), CommandId::AnimeEpisodes),
+            (format!("{}339:2", PICTURES_BUTTON_PREFIX), CommandId::AnimePictures),
+            (format!("{}339:2", REVIEWS_BUTTON_PREFIX), CommandId::AnimeReviews),
+            (format!("{}339:2", REVIEW_FULL_BUTTON_PREFIX), CommandId::AnimeReviews),
+            (format!("{}2", HELP_BUTTON_PREFIX), CommandId::Help),
+            (format!("{}42:details", PICK_MENU_PREFIX), CommandId::AnimeDetails),
+            (format!("{}42:recommendations", PICK_MENU_PREFIX), CommandId::AnimeRecommendations),
+        ];
+        for (custom_id, command) in posted {
+            assert_eq!(component_command(&custom_id), Some(command), "{}", custom_id);
+        }
+        assert_eq!(component_command(&format!("{}42:stats", PICK_MENU_PREFIX)), None);
+        // Menus have to say who may pick from them.
+        assert_eq!(component_command(&format!("{}details", PICK_MENU_PREFIX)), None);
+        assert_eq!(component_command(&format!("{}0:details", PICK_MENU_PREFIX)), None);
+        assert_eq!(component_command("unknown:1"), None);
+    }
+
+    #[test]
+    fn forged_page_numbers_are_rejected() {
+        assert_eq!(first_index(1, 10), Some(0));
+        assert_eq!(first_index(3, 25), Some(50));
+        assert_eq!(first_index(0, 10), None);
+        assert_eq!(first_index(u32::MAX, 10), None);
+        // The last page whose entries all have an index.
+        assert_eq!(first_index(u32::MAX / 10, 10), Some(u32::MAX / 10 * 10 - 10));
+        assert_eq!(first_index(u32::MAX / 10 + 1, 10), None);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn random_score_filter_must_be_a_finite_number() {
+        let options = parse_random_options("manga score=7.5", Locale::En).unwrap();
+        assert_eq!(options.min_score, Some(7.5));
+        for value in ["NaN", "inf", "-infinity", "seven"] {
+            let error = parse_random_options(&format!("score={}", value), Locale::En).err();
+            assert_eq!(error, Some(Locale::En.t_args("random-invalid-score", &[("value", value.to_lowercase().into())])));
+        }
+    }
+}
@@ -0,0 +1,342 @@
+// Discord's embed limits, enforced on every embed the bot sends. Text is cut on grapheme
+// boundaries so Japanese text or emoji with modifiers are never split in half, and whatever
+// doesn't fit in one embed either spills over into follow-up embeds or, where the message is
+// edited in place, is cut off.
+
+use serenity::builder::CreateEmbed;
+use serenity::model::channel::{Embed, EmbedField};
+use unicode_segmentation::UnicodeSegmentation;
+
+// Limits in characters, from https://discord.com/developers/docs/resources/message#embed-object-embed-limits
+pub const TITLE_LIMIT: usize = 256;
+pub const DESCRIPTION_LIMIT: usize = 4096;
+pub const FIELD_COUNT_LIMIT: usize = 25;
+pub const FIELD_NAME_LIMIT: usize = 256;
+pub const FIELD_VALUE_LIMIT: usize = 1024;
+pub const FOOTER_LIMIT: usize = 2048;
+pub const AUTHOR_LIMIT: usize = 256;
+// Title, description, field names and values, footer and author together.
+pub const TOTAL_LIMIT: usize = 6000;
+
+const ELLIPSIS: &str = "...";
+// Discord rejects fields with an empty name or value.
+const BLANK: &str = "\u{200b}";
+
+fn length(text: &str) -> usize {
+    text.chars().count()
+}
+
+// The longest run of whole graphemes from the start of `text` that is at most `max_chars` long.
+fn grapheme_prefix(text: &str, max_chars: usize) -> &str {
+    let mut used = 0;
+    let mut end = 0;
+    for (offset, grapheme) in text.grapheme_indices(true) {
+        used += length(grapheme);
+        if used > max_chars {
+            break;
+        }
+        end = offset + grapheme.len();
+    }
+    &text[..end]
+}
+
+// Cuts `text` to at most `max_chars` characters, ending in "..." when anything was cut.
+pub fn truncate(text: &str, max_chars: usize) -> String {
+    if length(text) <= max_chars {
+        return text.to_string();
+    }
+    match max_chars.checked_sub(ELLIPSIS.len()) {
+        Some(budget) => format!("{}{}", grapheme_prefix(text, budget), ELLIPSIS),
+        None => grapheme_prefix(text, max_chars).to_string(),
+    }
+}
+
+// Splits `text` into pieces of at most `max_chars` characters that join back into `text`. Pieces
+// end at a line break where one is close enough to the limit, else at a grapheme boundary.
+pub fn split_text(text: &str, max_chars: usize) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let mut rest = text;
+    while length(rest) > max_chars {
+        let head = grapheme_prefix(rest, max_chars);
+        let cut = match head.rfind('\n') {
+            Some(newline) if newline >= head.len() / 2 => newline + 1,
+            // A single grapheme longer than the limit can't be split without breaking it.
+            _ if head.is_empty() => rest.graphemes(true).next().map_or(rest.len(), str::len),
+            _ => head.len(),
+        };
+        pieces.push(&rest[..cut]);
+        rest = &rest[cut..];
+    }
+    if !rest.is_empty() || pieces.is_empty() {
+        pieces.push(rest);
+    }
+    pieces
+}
+
+fn field_length(field: &EmbedField) -> usize {
+    length(&field.name) + length(&field.value)
+}
+
+// Everything counted towards `TOTAL_LIMIT`.
+fn total_length(embed: &Embed) -> usize {
+    embed.title.as_deref().map_or(0, length)
+        + embed.description.as_deref().map_or(0, length)
+        + embed.fields.iter().map(field_length).sum::<usize>()
+        + embed
+            .footer
+            .as_ref()
+            .map_or(0, |footer| length(&footer.text))
+        + embed
+            .author
+            .as_ref()
+            .map_or(0, |author| length(&author.name))
+}
+
+// The builder keeps its embed private, so it's read back through its JSON form.
+fn to_model(embed: &CreateEmbed) -> Option<Embed> {
+    match serde_json::to_value(embed).and_then(serde_json::from_value) {
+        Ok(model) => Some(model),
+        Err(e) => {
+            eprintln!("Error reading embed for limit checks: {}", e);
+            None
+        }
+    }
+}
+
+// Cuts every piece of text to its own limit.
+fn truncate_parts(embed: &mut Embed) {
+    if let Some(title) = &mut embed.title {
+        *title = truncate(title, TITLE_LIMIT);
+    }
+    if let Some(footer) = &mut embed.footer {
+        footer.text = truncate(&footer.text, FOOTER_LIMIT);
+    }
+    if let Some(author) = &mut embed.author {
+        author.name = truncate(&author.name, AUTHOR_LIMIT);
+    }
+    for field in &mut embed.fields {
+        field.name = truncate(field.name.trim(), FIELD_NAME_LIMIT);
+        field.value = truncate(field.value.trim(), FIELD_VALUE_LIMIT);
+        if field.name.is_empty() {
+            field.name = BLANK.to_string();
+        }
+        if field.value.is_empty() {
+            field.value = BLANK.to_string();
+        }
+    }
+}
+
+// A follow-up embed for what didn't fit; it only keeps the color so it reads as part of the first.
+fn continuation(first: &Embed) -> Embed {
+    let mut embed = Embed::default();
+    embed.colour = first.colour;
+    embed
+}
+
+// Splits `embed` into as many embeds as it takes to respect every limit, in reading order: the
+// first keeps the title, images, footer and author, the others continue its description and
+// fields. Each has to be sent as its own message, since the total limit applies per message.
+pub fn split(embed: CreateEmbed) -> Vec<CreateEmbed> {
+    let Some(mut first) = to_model(&embed) else {
+        return vec![embed];
+    };
+    truncate_parts(&mut first);
+    let fields = std::mem::take(&mut first.fields);
+    let description = first.description.take().unwrap_or_default();
+
+    let mut embeds = Vec::new();
+    let mut current = first.clone();
+    // The title, footer and author take up to 2560 characters, so the first embed has room
+    // for a shorter description.
+    let first_room = DESCRIPTION_LIMIT.min(TOTAL_LIMIT - total_length(&first));
+    let mut rest = description.as_str();
+    if !rest.is_empty() {
+        let head = split_text(rest, first_room)[0];
+        current.description = Some(head.to_string());
+        rest = &rest[head.len()..];
+    }
+    for piece in split_text(rest, DESCRIPTION_LIMIT) {
+        if piece.is_empty() {
+            continue;
+        }
+        embeds.push(std::mem::replace(&mut current, continuation(&first)));
+        current.description = Some(piece.to_string());
+    }
+
+    for field in fields {
+        if current.fields.len() == FIELD_COUNT_LIMIT
+            || total_length(&current) + field_length(&field) > TOTAL_LIMIT
+        {
+            embeds.push(std::mem::replace(&mut current, continuation(&first)));
+        }
+        current.fields.push(field);
+    }
+    embeds.push(current);
+    embeds.into_iter().map(CreateEmbed::from).collect()
+}
+
+// Makes `embed` respect every limit on its own, for messages that are edited in place and can't
+// grow follow-ups: the description is cut and fields past the limits are left out.
+pub fn fit(embed: CreateEmbed) -> CreateEmbed {
+    let Some(mut model) = to_model(&embed) else {
+        return embed;
+    };
+    truncate_parts(&mut model);
+    let fields = std::mem::take(&mut model.fields);
+    if let Some(description) = model.description.take() {
+        let room = DESCRIPTION_LIMIT.min(TOTAL_LIMIT - total_length(&model));
+        model.description = Some(truncate(&description, room));
+    }
+    for field in fields {
+        if model.fields.len() == FIELD_COUNT_LIMIT
+            || total_length(&model) + field_length(&field) > TOTAL_LIMIT
+        {
+            break;
+        }
+        model.fields.push(field);
+    }
+    CreateEmbed::from(model)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use serenity::builder::{CreateEmbedAuthor, CreateEmbedFooter};
+
+    fn within_limits(embed: &Embed) -> bool {
+        embed.title.as_deref().map_or(0, length) <= TITLE_LIMIT
+            && embed.description.as_deref().map_or(0, length) <= DESCRIPTION_LIMIT
+            && embed.fields.len() <= FIELD_COUNT_LIMIT
+            && embed.fields.iter().all(|field| {
+                (1..=FIELD_NAME_LIMIT).contains(&length(&field.name))
+                    && (1..=FIELD_VALUE_LIMIT).contains(&length(&field.value))
+            })
+            && embed
+                .footer
+                .as_ref()
+                .map_or(0, |footer| length(&footer.text))
+                <= FOOTER_LIMIT
+            && embed
+                .author
+                .as_ref()
+                .map_or(0, |author| length(&author.name))
+                <= AUTHOR_LIMIT
+            && total_length(embed) <= TOTAL_LIMIT
+    }
+
+    // Text mixing ASCII, Japanese, combining marks, emoji with modifiers and line breaks.
+    fn text(max_len: usize) -> impl Strategy<Value = String> {
+        proptest::collection::vec(
+            prop_oneof![
+                "[a-z ]{1,8}",
+                "[ぁ-んァ-ン一-龯]{1,8}",
+                Just("e\u{301}".to_string()),
+                Just("👍🏽".to_string()),
+                Just("👨‍👩‍👧".to_string()),
+                Just("\n".to_string()),
+            ],
+            0..max_len,
+        )
+        .prop_map(|parts| parts.concat())
+    }
+
+    // Text long enough to break any limit, built by repeating a short sample.
+    fn long_text(max_repeats: usize) -> impl Strategy<Value = String> {
+        (text(20), 0..max_repeats).prop_map(|(sample, repeats)| sample.repeat(repeats))
+    }
+
+    fn embed() -> impl Strategy<Value = CreateEmbed> {
+        (
+            long_text(30),
+            long_text(400),
+            proptest::collection::vec((long_text(30), long_text(100), any::<bool>()), 0..40),
+            long_text(200),
+            long_text(30),
+        )
+            .prop_map(|(title, description, fields, footer, author)| {
+                let mut embed = CreateEmbed::new()
+                    .title(title)
+                    .description(description)
+                    .footer(CreateEmbedFooter::new(footer))
+                    .author(CreateEmbedAuthor::new(author))
+                    .color(0x5865F2);
+                for (name, value, inline) in fields {
+                    embed = embed.field(name, value, inline);
+                }
+                embed
+            })
+    }
+
+    fn model(embed: &CreateEmbed) -> Embed {
+        to_model(embed).expect("builder embeds read back")
+    }
+
+    proptest! {
+        #[test]
+        fn truncation_keeps_whole_graphemes(text in text(200), max_chars in 0usize..300) {
+            let cut = truncate(&text, max_chars);
+            prop_assert!(length(&cut) <= max_chars);
+            let kept = cut.strip_suffix(ELLIPSIS).filter(|_| cut != text).unwrap_or(&cut);
+            prop_assert!(text.starts_with(kept));
+            let graphemes: Vec<&str> = text.graphemes(true).collect();
+            let kept_graphemes: Vec<&str> = kept.graphemes(true).collect();
+            prop_assert_eq!(&graphemes[..kept_graphemes.len()], &kept_graphemes[..]);
+        }
+
+        #[test]
+        fn split_text_is_lossless(text in text(300), max_chars in 1usize..100) {
+            let pieces = split_text(&text, max_chars);
+            prop_assert_eq!(pieces.concat(), text.clone());
+            for piece in &pieces {
+                prop_assert!(length(piece) <= max_chars || piece.graphemes(true).count() == 1);
+            }
+        }
+    }
+
+    proptest! {
+        // Whole embeds are slow to check in debug builds.
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn split_embeds_respect_limits_and_keep_every_field(embed in embed()) {
+            let original = model(&embed);
+            let embeds: Vec<Embed> = split(embed).iter().map(model).collect();
+            for part in &embeds {
+                prop_assert!(within_limits(part));
+            }
+            let fields: usize = embeds.iter().map(|part| part.fields.len()).sum();
+            prop_assert_eq!(fields, original.fields.len());
+            let description: String = embeds.iter().filter_map(|part| part.description.clone()).collect();
+            prop_assert_eq!(description, original.description.unwrap_or_default());
+            prop_assert_eq!(&embeds[0].title, &original.title.map(|title| truncate(&title, TITLE_LIMIT)));
+        }
+
+        #[test]
+        fn fitted_embeds_respect_limits(embed in embed()) {
+            prop_assert!(within_limits(&model(&fit(embed))));
+        }
+    }
+
+    #[test]
+    fn multi_byte_synopses_are_cut_safely() {
+        let synopsis = "「シュタインズ・ゲート」".repeat(500);
+        let cut = truncate(&synopsis, 1000);
+        assert_eq!(length(&cut), 1000);
+        assert!(cut.ends_with(ELLIPSIS));
+    }
+
+    #[test]
+    fn oversized_embeds_spill_over() {
+        let mut embed = CreateEmbed::new().title("Fields").color(0xFF0000);
+        for i in 0..30 {
+            embed = embed.field(format!("Field {}", i), "x".repeat(1500), false);
+        }
+        let embeds: Vec<Embed> = split(embed).iter().map(model).collect();
+        assert!(embeds.len() > 1);
+        assert!(embeds.iter().all(within_limits));
+        assert_eq!(embeds[1].title, None);
+        assert_eq!(embeds[1].colour, embeds[0].colour);
+        assert_eq!(embeds[0].fields[0].value.chars().count(), FIELD_VALUE_LIMIT);
+    }
+}
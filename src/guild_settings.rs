@@ -0,0 +1,463 @@
+// Per-guild settings changed with `!config`. They're kept in memory after the first read and
+// written back to disk on every change.
+
+use crate::commands::{self, CommandSpec};
+use crate::i18n::Locale;
+use serde::{Deserialize, Serialize};
+use serenity::builder::CreateEmbed;
+use serenity::model::id::{ChannelId, GuildId, RoleId};
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tokio::fs;
+use tokio::sync::Mutex;
+
+const SETTINGS_FILE: &str = "data/guild_settings.json";
+const DEFAULT_PREFIX: &str = "!";
+pub const MAX_PREFIX_LEN: usize = 5;
+const MAX_COOLDOWN_SECS: u64 = 3600;
+
+// Every key `!config set` accepts, in the order `!config show` lists them.
+pub const KEYS: [&str; 6] = [
+    "prefix",
+    "language",
+    "nsfw",
+    "allowed_channels",
+    "embed_color",
+    "cooldowns",
+];
+
+// The lists `!perms` changes. `allowed_channels` can be set with `!config` too.
+pub const PERMISSION_KEYS: [&str; 4] = [
+    "allowed_channels",
+    "denied_channels",
+    "allowed_roles",
+    "denied_roles",
+];
+
+// `None` until the settings file has been read.
+static SETTINGS: Mutex<Option<BTreeMap<u64, GuildSettings>>> = Mutex::const_new(None);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum NsfwPolicy {
+    // Adult entries only show up in age-restricted channels.
+    #[default]
+    AgeRestricted,
+    // Adult entries never show up.
+    Block,
+    // Adult entries show up everywhere.
+    Allow,
+}
+
+impl NsfwPolicy {
+    fn name(self) -> &'static str {
+        match self {
+            NsfwPolicy::AgeRestricted => "age_restricted",
+            NsfwPolicy::Block => "block",
+            NsfwPolicy::Allow => "allow",
+        }
+    }
+}
+
+// A server's own cooldowns for a command, in seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cooldown {
+    pub user: u64,
+    pub guild: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GuildSettings {
+    pub prefix: String,
+    // Users can override it for themselves with `!language`.
+    pub language: Locale,
+    pub nsfw: NsfwPolicy,
+    // Channels commands are answered in; empty means every channel.
+    pub allowed_channels: Vec<u64>,
+    // Channels commands are never answered in, even if they're allowed.
+    pub denied_channels: Vec<u64>,
+    // Roles that may use heavy commands; empty means everyone.
+    pub allowed_roles: Vec<u64>,
+    // Roles that may not use heavy commands, even if another role allows them.
+    pub denied_roles: Vec<u64>,
+    pub embed_color: Option<u32>,
+    // Replaces the registry's cooldowns, by command name.
+    pub cooldowns: BTreeMap<String, Cooldown>,
+}
+
+impl Default for GuildSettings {
+    fn default() -> Self {
+        GuildSettings {
+            prefix: DEFAULT_PREFIX.to_string(),
+            language: Locale::default(),
+            nsfw: NsfwPolicy::default(),
+            allowed_channels: Vec::new(),
+            denied_channels: Vec::new(),
+            allowed_roles: Vec::new(),
+            denied_roles: Vec::new(),
+            embed_color: None,
+            cooldowns: BTreeMap::new(),
+        }
+    }
+}
+
+impl GuildSettings {
+    pub fn allows_channel(&self, channel_id: ChannelId) -> bool {
+        (self.allowed_channels.is_empty() || self.allowed_channels.contains(&channel_id.get()))
+            && !self.denied_channels.contains(&channel_id.get())
+    }
+
+    pub fn limits_roles(&self) -> bool {
+        !self.allowed_roles.is_empty() || !self.denied_roles.is_empty()
+    }
+
+    // Whether a member with `roles` may use heavy commands.
+    pub fn allows_roles(&self, roles: &[RoleId]) -> bool {
+        let has_any = |list: &[u64]| roles.iter().any(|role| list.contains(&role.get()));
+        (self.allowed_roles.is_empty() || has_any(&self.allowed_roles))
+            && !has_any(&self.denied_roles)
+    }
+
+    // The per-user and per-server cooldowns of a command on this server.
+    pub fn cooldowns_for(&self, spec: &CommandSpec) -> (Duration, Duration) {
+        match self.cooldowns.get(spec.name) {
+            Some(cooldown) => (
+                Duration::from_secs(cooldown.user),
+                Duration::from_secs(cooldown.guild),
+            ),
+            None => (spec.cooldown, spec.guild_cooldown),
+        }
+    }
+
+    pub fn style(&self, embed: CreateEmbed) -> CreateEmbed {
+        match self.embed_color {
+            Some(color) => embed.color(color),
+            None => embed,
+        }
+    }
+
+    // The current value of a `KEYS` entry, as `!config show` displays it.
+    pub fn value(&self, key: &str) -> String {
+        match key {
+            "prefix" => format!("`{}`", self.prefix),
+            "language" => self.language.code().to_string(),
+            "nsfw" => self.nsfw.name().to_string(),
+            "allowed_channels" => mentions(&self.allowed_channels, "<#", "all"),
+            "denied_channels" => mentions(&self.denied_channels, "<#", "none"),
+            "allowed_roles" => mentions(&self.allowed_roles, "<@&", "all"),
+            "denied_roles" => mentions(&self.denied_roles, "<@&", "none"),
+            "embed_color" => match self.embed_color {
+                Some(color) => format!("#{:06X}", color),
+                None => "default".to_string(),
+            },
+            "cooldowns" if self.cooldowns.is_empty() => "default".to_string(),
+            "cooldowns" => self
+                .cooldowns
+                .iter()
+                .map(|(name, cooldown)| {
+                    format!("`{}` {}s/{}s", name, cooldown.user, cooldown.guild)
+                })
+                .collect::<Vec<String>>()
+                .join(", "),
+            _ => String::new(),
+        }
+    }
+
+    // Applies `!config set <key> <value>`. The error is the catalog key explaining what's wrong
+    // with the value.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), &'static str> {
+        let value = value.trim();
+        match key {
+            "prefix" => {
+                if value.is_empty()
+                    || value.chars().count() > MAX_PREFIX_LEN
+                    || value.chars().any(char::is_whitespace)
+                {
+                    return Err("setting-error-prefix");
+                }
+                self.prefix = value.to_string();
+            }
+            "language" => {
+                self.language = Locale::from_code(value).ok_or("setting-error-language")?;
+            }
+            "nsfw" => {
+                self.nsfw = match value.to_lowercase().as_str() {
+                    "age_restricted" => NsfwPolicy::AgeRestricted,
+                    "block" => NsfwPolicy::Block,
+                    "allow" => NsfwPolicy::Allow,
+                    _ => return Err("setting-error-nsfw"),
+                };
+            }
+            "allowed_channels" => {
+                self.allowed_channels =
+                    parse_ids(value, "<#", "all").ok_or("setting-error-channels")?;
+            }
+            "denied_channels" => {
+                self.denied_channels =
+                    parse_ids(value, "<#", "none").ok_or("setting-error-denied-channels")?;
+            }
+            "allowed_roles" => {
+                self.allowed_roles = parse_ids(value, "<@&", "all").ok_or("setting-error-roles")?;
+            }
+            "denied_roles" => {
+                self.denied_roles =
+                    parse_ids(value, "<@&", "none").ok_or("setting-error-denied-roles")?;
+            }
+            "embed_color" => {
+                if value.eq_ignore_ascii_case("default") {
+                    self.embed_color = None;
+                    return Ok(());
+                }
+                let hex = value.trim_start_matches('#');
+                match u32::from_str_radix(hex, 16) {
+                    Ok(color) if hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()) => {
+                        self.embed_color = Some(color)
+                    }
+                    _ => return Err("setting-error-color"),
+                }
+            }
+            "cooldowns" => match parse_cooldown(value).ok_or("setting-error-cooldown")? {
+                (name, Some(cooldown)) => {
+                    self.cooldowns.insert(name.to_string(), cooldown);
+                }
+                (name, None) => {
+                    self.cooldowns.remove(name);
+                }
+            },
+            _ => return Err("setting-error-unknown"),
+        }
+        Ok(())
+    }
+}
+
+// A list of channels or roles as mentions, or `empty` when there are none.
+fn mentions(ids: &[u64], mention: &str, empty: &str) -> String {
+    if ids.is_empty() {
+        return empty.to_string();
+    }
+    ids.iter()
+        .map(|id| format!("{}{}>", mention, id))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+// Parses mentions like `<#123>` or bare IDs separated by spaces or commas. `clear` empties the
+// list.
+fn parse_ids(value: &str, mention: &str, clear: &str) -> Option<Vec<u64>> {
+    if value.eq_ignore_ascii_case(clear) {
+        return Some(Vec::new());
+    }
+    let ids: Vec<u64> = value
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            part.trim_start_matches(mention)
+                .trim_end_matches('>')
+                .parse::<u64>()
+                .ok()
+        })
+        .collect::<Option<Vec<u64>>>()?;
+    (!ids.is_empty()).then_some(ids)
+}
+
+// Parses `<command> <seconds per user> [seconds per server]`, or `<command> default` which gives
+// `None` to go back to the registry's cooldowns.
+fn parse_cooldown(value: &str) -> Option<(&'static str, Option<Cooldown>)> {
+    let words: Vec<&str> = value.split_whitespace().collect();
+    if let Some((last, command)) = words.split_last() {
+        if last.eq_ignore_ascii_case("default") {
+            return Some((commands::find_command(&command.join(" "))?.name, None));
+        }
+    }
+
+    let count = words
+        .iter()
+        .rev()
+        .take(2)
+        .take_while(|word| word.parse::<u64>().is_ok())
+        .count();
+    let (command, seconds) = words.split_at(words.len() - count);
+    let spec = commands::find_command(&command.join(" "))?;
+    let seconds: Vec<u64> = seconds
+        .iter()
+        .filter_map(|word| word.parse().ok())
+        .collect();
+    let cooldown = Cooldown {
+        user: *seconds.first()?,
+        guild: seconds.get(1).copied().unwrap_or(0),
+    };
+    if cooldown.user.max(cooldown.guild) > MAX_COOLDOWN_SECS {
+        return None;
+    }
+    Some((spec.name, Some(cooldown)))
+}
+
+async fn load_settings() -> BTreeMap<u64, GuildSettings> {
+    match fs::read_to_string(SETTINGS_FILE).await {
+        Ok(data) => serde_json::from_str(&data).unwrap_or_default(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+        Err(e) => {
+            eprintln!("Error loading guild settings file: {}", e);
+            BTreeMap::new()
+        }
+    }
+}
+
+async fn save_settings(settings: &BTreeMap<u64, GuildSettings>) {
+    let json_data =
+        serde_json::to_string_pretty(settings).expect("Failed to serialize guild settings");
+    if let Err(dir_err) = fs::create_dir_all("data").await {
+        eprintln!("Error creating 'data' directory before saving: {}", dir_err);
+        return;
+    }
+    if let Err(e) = fs::write(SETTINGS_FILE, json_data).await {
+        eprintln!("Error saving guild settings to file: {}", e);
+    }
+}
+
+// Settings for a guild; direct messages and unconfigured guilds get the defaults.
+pub async fn get(guild_id: Option<GuildId>) -> GuildSettings {
+    let Some(guild_id) = guild_id else {
+        return GuildSettings::default();
+    };
+    let mut cache = SETTINGS.lock().await;
+    if cache.is_none() {
+        *cache = Some(load_settings().await);
+    }
+    cache
+        .as_ref()
+        .and_then(|settings| settings.get(&guild_id.get()))
+        .cloned()
+        .unwrap_or_default()
+}
+
+// Changes one setting of a guild and saves it, returning the updated settings.
+pub async fn update(
+    guild_id: GuildId,
+    key: &str,
+    value: &str,
+) -> Result<GuildSettings, &'static str> {
+    let mut cache = SETTINGS.lock().await;
+    if cache.is_none() {
+        *cache = Some(load_settings().await);
+    }
+    let all = cache.get_or_insert_with(BTreeMap::new);
+    let mut settings = all.get(&guild_id.get()).cloned().unwrap_or_default();
+    settings.set(key, value)?;
+    all.insert(guild_id.get(), settings.clone());
+    save_settings(all).await;
+    Ok(settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_key_can_be_shown() {
+        let settings = GuildSettings::default();
+        for key in KEYS.into_iter().chain(PERMISSION_KEYS) {
+            assert!(!settings.value(key).is_empty(), "{}", key);
+        }
+    }
+
+    #[test]
+    fn values_are_validated() {
+        let mut settings = GuildSettings::default();
+        assert!(settings.set("prefix", "?").is_ok());
+        assert_eq!(settings.prefix, "?");
+        assert!(settings.set("prefix", "a b").is_err());
+        assert!(settings.set("prefix", "toolong").is_err());
+
+        assert!(settings.set("language", "TR").is_ok());
+        assert_eq!(settings.language, Locale::Tr);
+        assert!(settings.set("language", "xx").is_err());
+
+        assert!(settings.set("nsfw", "block").is_ok());
+        assert_eq!(settings.nsfw, NsfwPolicy::Block);
+        assert!(settings.set("nsfw", "maybe").is_err());
+
+        assert!(settings.set("unknown", "1").is_err());
+    }
+
+    #[test]
+    fn allowed_channels_accept_mentions_and_ids() {
+        let mut settings = GuildSettings::default();
+        assert!(settings.allows_channel(ChannelId::new(5)));
+        settings.set("allowed_channels", "<#123>, 456").unwrap();
+        assert_eq!(settings.allowed_channels, vec![123, 456]);
+        assert!(!settings.allows_channel(ChannelId::new(5)));
+        assert!(settings.set("allowed_channels", "#general").is_err());
+        settings.set("allowed_channels", "all").unwrap();
+        assert!(settings.allowed_channels.is_empty());
+    }
+
+    #[test]
+    fn denied_channels_and_roles_win() {
+        let mut settings = GuildSettings::default();
+        assert!(!settings.limits_roles());
+        assert!(settings.allows_roles(&[]));
+        settings.set("denied_channels", "<#5>").unwrap();
+        assert!(!settings.allows_channel(ChannelId::new(5)));
+        assert!(settings.allows_channel(ChannelId::new(6)));
+        assert!(settings.set("denied_channels", "all").is_err());
+
+        settings.set("allowed_roles", "<@&1> 2").unwrap();
+        settings.set("denied_roles", "3").unwrap();
+        assert!(settings.limits_roles());
+        assert_eq!(settings.value("allowed_roles"), "<@&1>, <@&2>");
+        assert!(settings.allows_roles(&[RoleId::new(2)]));
+        assert!(!settings.allows_roles(&[RoleId::new(4)]));
+        assert!(!settings.allows_roles(&[RoleId::new(1), RoleId::new(3)]));
+        settings.set("allowed_roles", "all").unwrap();
+        settings.set("denied_roles", "none").unwrap();
+        assert!(!settings.limits_roles());
+        assert!(settings.allows_roles(&[RoleId::new(4)]));
+    }
+
+    #[test]
+    fn cooldowns_can_be_overridden_per_command() {
+        let mut settings = GuildSettings::default();
+        let watch_order = commands::find_command("anime watchorder").unwrap();
+        assert_eq!(
+            settings.cooldowns_for(watch_order),
+            (watch_order.cooldown, watch_order.guild_cooldown)
+        );
+
+        settings.set("cooldowns", "anime order 60 20").unwrap();
+        assert_eq!(
+            settings.cooldowns_for(watch_order),
+            (Duration::from_secs(60), Duration::from_secs(20))
+        );
+        settings.set("cooldowns", "genre 0").unwrap();
+        assert_eq!(
+            settings.value("cooldowns"),
+            "`anime watchorder` 60s/20s, `genre` 0s/0s"
+        );
+
+        assert!(settings.set("cooldowns", "anime watchorder").is_err());
+        assert!(settings.set("cooldowns", "nothing 5").is_err());
+        assert!(settings.set("cooldowns", "genre 99999").is_err());
+
+        settings
+            .set("cooldowns", "anime watchorder default")
+            .unwrap();
+        assert_eq!(
+            settings.cooldowns_for(watch_order),
+            (watch_order.cooldown, watch_order.guild_cooldown)
+        );
+    }
+
+    #[test]
+    fn embed_colors_are_hex() {
+        let mut settings = GuildSettings::default();
+        settings.set("embed_color", "#5865f2").unwrap();
+        assert_eq!(settings.embed_color, Some(0x5865F2));
+        assert_eq!(settings.value("embed_color"), "#5865F2");
+        assert!(settings.set("embed_color", "blue").is_err());
+        assert!(settings.set("embed_color", "#FFF").is_err());
+        settings.set("embed_color", "default").unwrap();
+        assert_eq!(settings.embed_color, None);
+    }
+}
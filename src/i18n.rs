@@ -0,0 +1,249 @@
+// Message catalogs for everything the bot says. Each language is a Fluent file under `locales/`
+// compiled into the binary. A key missing from a translation falls back to English, and a key
+// missing from English falls back to the key itself so the gap is visible instead of fatal.
+
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue};
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+use unic_langid::LanguageIdentifier;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    Tr,
+}
+
+// Every supported language, in the order they're listed to users. English comes first since it's
+// the fallback.
+pub const LOCALES: [Locale; 2] = [Locale::En, Locale::Tr];
+
+static BUNDLES: OnceLock<Vec<FluentBundle<FluentResource>>> = OnceLock::new();
+
+impl Locale {
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Tr => "tr",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Locale> {
+        LOCALES
+            .into_iter()
+            .find(|locale| locale.code().eq_ignore_ascii_case(code.trim()))
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Locale::En => include_str!("../locales/en.ftl"),
+            Locale::Tr => include_str!("../locales/tr.ftl"),
+        }
+    }
+
+    fn bundle(self) -> &'static FluentBundle<FluentResource> {
+        let bundles = BUNDLES.get_or_init(|| LOCALES.iter().map(|locale| build_bundle(*locale)).collect());
+        &bundles[self as usize]
+    }
+
+    // The message for `key` in this language.
+    pub fn t(self, key: &str) -> String {
+        self.t_args(key, &[])
+    }
+
+    // The message for `key` with its `{ $name }` placeholders filled in from `args`.
+    pub fn t_args(self, key: &str, args: &[(&str, FluentValue)]) -> String {
+        let mut fluent_args = FluentArgs::new();
+        for (name, value) in args {
+            fluent_args.set(*name, value.clone());
+        }
+
+        for locale in [self, Locale::En] {
+            let bundle = locale.bundle();
+            let Some(pattern) = bundle.get_message(key).and_then(|message| message.value()) else {
+                continue;
+            };
+            let mut errors = Vec::new();
+            let text = bundle.format_pattern(pattern, Some(&fluent_args), &mut errors);
+            if !errors.is_empty() {
+                eprintln!("Error formatting message '{}' in {}: {:?}", key, locale.code(), errors);
+            }
+            return text.into_owned();
+        }
+
+        eprintln!("Missing message '{}'", key);
+        key.to_string()
+    }
+}
+
+fn build_bundle(locale: Locale) -> FluentBundle<FluentResource> {
+    let language: LanguageIdentifier = locale.code().parse().expect("Locale codes are valid language tags");
+    let mut bundle = FluentBundle::new_concurrent(vec![language]);
+    // Discord shows the Unicode isolation marks Fluent puts around placeables as stray characters.
+    bundle.set_use_isolating(false);
+
+    let resource = match FluentResource::try_new(locale.source().to_string()) {
+        Ok(resource) => resource,
+        Err((resource, errors)) => {
+            eprintln!("Error parsing the {} catalog: {:?}", locale.code(), errors);
+            resource
+        }
+    };
+    if let Err(errors) = bundle.add_resource(resource) {
+        eprintln!("Error loading the {} catalog: {:?}", locale.code(), errors);
+    }
+    bundle
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{CATEGORIES, COMMANDS};
+    use std::collections::{BTreeMap, BTreeSet};
+
+    // Message keys of a catalog mapped to the variables they use. Only plain `key = value`
+    // messages are used in the catalogs, so a line-based read is enough.
+    fn messages(source: &str) -> BTreeMap<String, BTreeSet<String>> {
+        let mut messages: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        let mut current = None;
+        for line in source.lines() {
+            if line.starts_with('#') || line.trim().is_empty() {
+                continue;
+            }
+            let text = match line.split_once(" =") {
+                Some((key, value)) if !line.starts_with(' ') => {
+                    current = Some(key.to_string());
+                    messages.entry(key.to_string()).or_default();
+                    value
+                }
+                _ => line,
+            };
+            let Some(key) = &current else {
+                continue;
+            };
+            for part in text.split('$').skip(1) {
+                let variable: String = part
+                    .chars()
+                    .take_while(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '-')
+                    .collect();
+                messages.get_mut(key).unwrap().insert(variable);
+            }
+        }
+        messages
+    }
+
+    // Keys written as literals in a source file: passed to `t` and `t_args`, or returned as errors
+    // for the caller to translate.
+    fn keys_used_in(source: &str) -> Vec<String> {
+        let mut keys = Vec::new();
+        for pattern in [".t(\"", ".t_args(\"", "Err(\"", "ok_or(\"", "usage_error(\""] {
+            for part in source.split(pattern).skip(1) {
+                keys.push(part.split('"').next().unwrap_or_default().to_string());
+            }
+        }
+        keys
+    }
+
+    #[test]
+    fn catalogs_parse_without_errors() {
+        for locale in LOCALES {
+            assert!(
+                FluentResource::try_new(locale.source().to_string()).is_ok(),
+                "{}",
+                locale.code()
+            );
+        }
+    }
+
+    #[test]
+    fn every_locale_has_every_key() {
+        let english = messages(Locale::En.source());
+        for locale in LOCALES {
+            let translated = messages(locale.source());
+            for (key, variables) in &english {
+                match translated.get(key) {
+                    None => panic!("'{}' is missing from the {} catalog", key, locale.code()),
+                    Some(translated_variables) => assert_eq!(
+                        translated_variables,
+                        variables,
+                        "'{}' uses different variables in the {} catalog",
+                        key,
+                        locale.code()
+                    ),
+                }
+            }
+            for key in translated.keys() {
+                assert!(
+                    english.contains_key(key),
+                    "'{}' in the {} catalog doesn't exist in English",
+                    key,
+                    locale.code()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn every_key_used_in_code_exists() {
+        let english = messages(Locale::En.source());
+        let sources = [
+            include_str!("discord_handler.rs"),
+            include_str!("commands.rs"),
+            include_str!("guild_settings.rs"),
+            include_str!("news_feed.rs"),
+        ];
+        let mut used: Vec<String> = sources.iter().flat_map(|source| keys_used_in(source)).collect();
+        for spec in COMMANDS {
+            used.push(spec.help.to_string());
+            used.extend(spec.args.iter().map(|arg| arg.description.to_string()));
+        }
+        used.extend(CATEGORIES.iter().map(|category| category.title_key().to_string()));
+        // Keys picked from tables or passed through helpers, which the literal scan can't see.
+        used.extend(
+            [
+                "genres-heading-genres",
+                "genres-heading-themes",
+                "genres-heading-demographics",
+                "field-openings",
+                "field-endings",
+                "compare-shared-anime",
+                "compare-shared-manga",
+                "compare-shared-characters",
+                "field-score",
+                "field-rank",
+                "field-popularity",
+                "field-members",
+            ]
+            .map(String::from),
+        );
+
+        assert!(used.len() > 100, "the key scan found suspiciously few keys");
+        for key in used {
+            assert!(english.contains_key(&key), "'{}' is used but not in the catalogs", key);
+        }
+    }
+
+    #[test]
+    fn placeholders_are_filled_and_missing_keys_fall_back() {
+        assert_eq!(
+            Locale::En.t_args("anime-not-found", &[("query", "Lain".into())]),
+            "No anime found for 'Lain'."
+        );
+        assert_eq!(
+            Locale::Tr.t_args("anime-not-found", &[("query", "Lain".into())]),
+            "'Lain' için anime bulunamadı."
+        );
+        assert_eq!(Locale::Tr.t("no-such-key"), "no-such-key");
+    }
+
+    #[test]
+    fn locale_codes_round_trip() {
+        for locale in LOCALES {
+            assert_eq!(Locale::from_code(locale.code()), Some(locale));
+        }
+        assert_eq!(Locale::from_code(" TR "), Some(Locale::Tr));
+        assert_eq!(Locale::from_code("xx"), None);
+    }
+}
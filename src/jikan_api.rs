@@ -1,15 +1,48 @@
+use crate::jikan_rate_limit;
 use reqwest::Client;
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+const JIKAN_BASE_URL: &str = "https://api.jikan.moe/v4";
+
+// How long a cached response body stays fresh, and how many bodies are kept at most.
+const CACHE_TTL: Duration = Duration::from_secs(30 * 60);
+const CACHE_CAPACITY: usize = 500;
+
+// Response bodies of endpoints whose data rarely changes, keyed by request URL and query.
+static RESPONSE_CACHE: Mutex<BTreeMap<String, (Instant, String)>> =
+    Mutex::const_new(BTreeMap::new());
+
+// Relation types that don't belong in a watch order: shared characters and loose connections.
+const WATCH_ORDER_SKIPPED_RELATIONS: [&str; 2] = ["Character", "Other"];
 
 #[derive(Debug, Deserialize)]
 pub struct Anime {
     pub mal_id: u32,
     pub title: String,
+    pub title_english: Option<String>,
+    #[serde(default)]
+    pub titles: Vec<Title>,
+    #[serde(rename = "type")]
+    pub anime_type: Option<String>,
+    pub year: Option<u32>,
+    pub score: Option<f32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Pagination {
+    pub last_visible_page: u32,
+    pub has_next_page: bool,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct JikanSearchResponse {
     pub data: Vec<Anime>,
+    pub pagination: Option<Pagination>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -22,21 +55,78 @@ pub struct Aired {
 #[derive(Debug, Deserialize)]
 pub struct ImageUrls {
     pub image_url: Option<String>,
+    pub large_image_url: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct CommonImageResource {
     pub jpg: Option<ImageUrls>,
+    pub webp: Option<ImageUrls>,
+}
+
+impl CommonImageResource {
+    // Largest picture available, preferring JPG over WebP and falling back to the regular size.
+    pub fn largest_url(&self) -> Option<&str> {
+        let formats = [self.jpg.as_ref(), self.webp.as_ref()];
+        formats
+            .iter()
+            .flatten()
+            .find_map(|f| f.large_image_url.as_deref())
+            .or_else(|| {
+                formats
+                    .iter()
+                    .flatten()
+                    .find_map(|f| f.image_url.as_deref())
+            })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct YoutubeImages {
+    pub large_image_url: Option<String>,
+    pub maximum_image_url: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct YoutubeMeta {
     pub youtube_id: Option<String>,
+    pub url: Option<String>,
+    pub images: Option<YoutubeImages>,
+}
+
+impl YoutubeMeta {
+    pub fn watch_url(&self) -> Option<String> {
+        self.url.clone().or_else(|| {
+            self.youtube_id
+                .as_ref()
+                .map(|id| format!("https://www.youtube.com/watch?v={}", id))
+        })
+    }
+
+    pub fn thumbnail_url(&self) -> Option<&str> {
+        let images = self.images.as_ref()?;
+        images
+            .maximum_image_url
+            .as_deref()
+            .or(images.large_image_url.as_deref())
+    }
 }
 
+#[derive(Debug, Deserialize)]
+pub struct MalUrl {
+    pub mal_id: u32,
+    #[serde(rename = "type")]
+    pub entry_type: String,
+    pub name: String,
+    pub url: String,
+}
+
+// One name of an entry, studio or producer. Jikan's types are "Default", "Synonym", "English",
+// "Japanese" and other languages like "German".
 #[derive(Debug, Deserialize)]
 pub struct Title {
     #[serde(rename = "type")]
+    pub title_type: String,
     pub title: String,
 }
 
@@ -47,11 +137,9 @@ pub struct AnimeDetails {
     pub images: Option<CommonImageResource>,
     pub trailer: Option<YoutubeMeta>,
     pub approved: Option<bool>,
+    // Every name of the entry by type; `title` is the "Default" one.
     pub titles: Vec<Title>,
     pub title: String,
-    pub title_english: Option<String>,
-    pub title_japanese: Option<String>,
-    pub title_synonyms: Vec<String>,
     #[serde(rename = "type")]
     pub anime_type: Option<String>,
     pub source: Option<String>,
@@ -68,6 +156,369 @@ pub struct AnimeDetails {
     pub members: Option<u32>,
     pub favorites: Option<u32>,
     pub synopsis: Option<String>,
+    #[serde(default)]
+    pub genres: Vec<MalUrl>,
+    #[serde(default)]
+    pub explicit_genres: Vec<MalUrl>,
+    #[serde(default)]
+    pub themes: Vec<MalUrl>,
+    #[serde(default)]
+    pub demographics: Vec<MalUrl>,
+    #[serde(default)]
+    pub studios: Vec<MalUrl>,
+    #[serde(default)]
+    pub producers: Vec<MalUrl>,
+    #[serde(default)]
+    pub licensors: Vec<MalUrl>,
+    // Only filled by the `/anime/{id}/full` endpoint.
+    #[serde(default)]
+    pub relations: Vec<Relation>,
+}
+
+impl AnimeDetails {
+    // Rx-rated and Hentai entries, which Jikan's `sfw` flag also filters out of searches.
+    pub fn is_adult(&self) -> bool {
+        self.rating.as_deref().is_some_and(|r| r.starts_with("Rx"))
+            || self.genres.iter().chain(&self.explicit_genres).any(|g| g.name == "Hentai")
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Relation {
+    pub relation: String,
+    pub entry: Vec<MalUrl>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JikanRelationsResponse {
+    pub data: Vec<Relation>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Episode {
+    pub mal_id: u32,
+    pub url: Option<String>,
+    pub title: String,
+    pub aired: Option<String>,
+    pub score: Option<f32>,
+    pub filler: bool,
+    pub recap: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JikanEpisodesResponse {
+    pub data: Vec<Episode>,
+    pub pagination: Pagination,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EpisodeDetails {
+    pub mal_id: u32,
+    pub url: String,
+    pub title: String,
+    pub title_japanese: Option<String>,
+    pub title_romanji: Option<String>,
+    // Length in seconds.
+    pub duration: Option<u32>,
+    pub aired: Option<String>,
+    pub filler: bool,
+    pub recap: bool,
+    pub synopsis: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JikanEpisodeResponse {
+    pub data: EpisodeDetails,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ScoreVotes {
+    pub score: u32,
+    pub votes: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AnimeStatistics {
+    pub watching: u32,
+    pub completed: u32,
+    pub on_hold: u32,
+    pub dropped: u32,
+    pub plan_to_watch: u32,
+    pub total: u32,
+    #[serde(default)]
+    pub scores: Vec<ScoreVotes>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JikanStatisticsResponse {
+    pub data: AnimeStatistics,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReviewUser {
+    pub username: String,
+    pub url: String,
+    pub images: Option<CommonImageResource>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Review {
+    pub url: String,
+    pub date: String,
+    pub review: String,
+    pub score: Option<u32>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub is_spoiler: bool,
+    #[serde(default)]
+    pub is_preliminary: bool,
+    pub episodes_watched: Option<u32>,
+    pub user: ReviewUser,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JikanReviewsResponse {
+    pub data: Vec<Review>,
+    pub pagination: Pagination,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NewsArticle {
+    pub mal_id: u32,
+    pub url: String,
+    pub title: String,
+    pub date: String,
+    pub author_username: Option<String>,
+    pub images: Option<CommonImageResource>,
+    pub comments: Option<u32>,
+    pub excerpt: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JikanNewsResponse {
+    pub data: Vec<NewsArticle>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JikanPicturesResponse {
+    pub data: Vec<CommonImageResource>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PromoVideo {
+    pub title: String,
+    pub trailer: YoutubeMeta,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MusicVideoMeta {
+    pub title: Option<String>,
+    pub author: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MusicVideo {
+    pub title: String,
+    pub video: YoutubeMeta,
+    pub meta: Option<MusicVideoMeta>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AnimeVideos {
+    #[serde(default)]
+    pub promo: Vec<PromoVideo>,
+    #[serde(default)]
+    pub music_videos: Vec<MusicVideo>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JikanVideosResponse {
+    pub data: AnimeVideos,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AnimeThemes {
+    #[serde(default)]
+    pub openings: Vec<String>,
+    #[serde(default)]
+    pub endings: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JikanThemesResponse {
+    pub data: AnimeThemes,
+}
+
+// One opening or ending, parsed from Jikan's display string.
+#[derive(Debug)]
+pub struct ThemeSong {
+    pub number: Option<u32>,
+    pub title: String,
+    pub artist: Option<String>,
+    pub episodes: Option<String>,
+}
+
+impl ThemeSong {
+    // Jikan sends themes as text such as `1: "Duvet" by bôa (eps 1-13)`; the number, artist and
+    // episode range are all optional. Unrecognized text ends up as the title unchanged.
+    pub fn parse(text: &str) -> ThemeSong {
+        let mut rest = text.trim();
+
+        let mut number = None;
+        if let Some((prefix, after)) = rest.split_once(':') {
+            if let Ok(n) = prefix.trim().trim_start_matches('#').parse::<u32>() {
+                number = Some(n);
+                rest = after.trim();
+            }
+        }
+
+        let mut episodes = None;
+        if let Some(open) = rest.rfind('(') {
+            let tail = &rest[open..];
+            if tail.starts_with("(ep") && tail.ends_with(')') {
+                episodes = Some(tail[1..tail.len() - 1].to_string());
+                rest = rest[..open].trim_end();
+            }
+        }
+
+        let (title, artist) = match rest.strip_prefix('"') {
+            Some(quoted) => match quoted.rfind("\" by ") {
+                Some(end) => (
+                    quoted[..end].to_string(),
+                    Some(quoted[end + 5..].trim().to_string()),
+                ),
+                None => (quoted.trim_end_matches('"').to_string(), None),
+            },
+            None => match rest.rsplit_once(" by ") {
+                Some((title, artist)) => (title.to_string(), Some(artist.trim().to_string())),
+                None => (rest.to_string(), None),
+            },
+        };
+
+        ThemeSong {
+            number,
+            title,
+            artist: artist.filter(|a| !a.is_empty()),
+            episodes,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UserAnimeStatistics {
+    pub days_watched: f32,
+    pub mean_score: f32,
+    pub watching: u32,
+    pub completed: u32,
+    pub on_hold: u32,
+    pub dropped: u32,
+    pub plan_to_watch: u32,
+    pub total_entries: u32,
+    pub episodes_watched: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UserMangaStatistics {
+    pub days_read: f32,
+    pub mean_score: f32,
+    pub reading: u32,
+    pub completed: u32,
+    pub on_hold: u32,
+    pub dropped: u32,
+    pub plan_to_read: u32,
+    pub total_entries: u32,
+    pub chapters_read: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UserStatistics {
+    pub anime: UserAnimeStatistics,
+    pub manga: UserMangaStatistics,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FavoriteEntry {
+    pub mal_id: u32,
+    pub url: String,
+    pub title: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FavoriteCharacter {
+    pub mal_id: u32,
+    pub url: String,
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct UserFavorites {
+    #[serde(default)]
+    pub anime: Vec<FavoriteEntry>,
+    #[serde(default)]
+    pub manga: Vec<FavoriteEntry>,
+    #[serde(default)]
+    pub characters: Vec<FavoriteCharacter>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateEntry {
+    pub url: String,
+    pub title: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AnimeListUpdate {
+    pub entry: UpdateEntry,
+    pub score: Option<u32>,
+    pub status: Option<String>,
+    pub episodes_seen: Option<u32>,
+    pub episodes_total: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MangaListUpdate {
+    pub entry: UpdateEntry,
+    pub score: Option<u32>,
+    pub status: Option<String>,
+    pub chapters_read: Option<u32>,
+    pub chapters_total: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct UserUpdates {
+    #[serde(default)]
+    pub anime: Vec<AnimeListUpdate>,
+    #[serde(default)]
+    pub manga: Vec<MangaListUpdate>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UserProfile {
+    pub username: String,
+    pub url: String,
+    pub images: Option<CommonImageResource>,
+    pub last_online: Option<String>,
+    pub joined: Option<String>,
+    pub location: Option<String>,
+    pub statistics: Option<UserStatistics>,
+    #[serde(default)]
+    pub favorites: UserFavorites,
+    #[serde(default)]
+    pub updates: UserUpdates,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JikanUserResponse {
+    pub data: UserProfile,
+}
+
+#[derive(Debug)]
+pub struct FranchiseWalk {
+    // Every anime reached, sorted by the date it started airing.
+    pub entries: Vec<AnimeDetails>,
+    // Set when the depth limit or request budget stopped the walk before the graph was exhausted.
+    pub incomplete: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -94,130 +545,518 @@ pub struct JikanRecommendationsResponse {
     pub data: Vec<RecommendationItem>,
 }
 
-pub async fn search_anime(query: &str) -> Option<Vec<Anime>> {
+#[derive(Debug, Deserialize)]
+pub struct MangaDetails {
+    pub mal_id: u32,
+    pub url: String,
+    pub images: Option<CommonImageResource>,
+    pub title: String,
+    pub title_english: Option<String>,
+    pub title_japanese: Option<String>,
+    #[serde(rename = "type")]
+    pub manga_type: Option<String>,
+    pub chapters: Option<u32>,
+    pub volumes: Option<u32>,
+    pub status: Option<String>,
+    pub published: Option<Aired>,
+    pub score: Option<f32>,
+    pub rank: Option<u32>,
+    pub popularity: Option<u32>,
+    pub members: Option<u32>,
+    pub favorites: Option<u32>,
+    pub synopsis: Option<String>,
+    #[serde(default)]
+    pub genres: Vec<MalUrl>,
+    #[serde(default)]
+    pub explicit_genres: Vec<MalUrl>,
+}
+
+impl MangaDetails {
+    // Jikan lists Hentai and Erotica as explicit genres.
+    pub fn is_adult(&self) -> bool {
+        !self.explicit_genres.is_empty()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JikanMangaResponse {
+    pub data: MangaDetails,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CharacterDetails {
+    pub mal_id: u32,
+    pub url: String,
+    pub images: Option<CommonImageResource>,
+    pub name: String,
+    pub name_kanji: Option<String>,
+    #[serde(default)]
+    pub nicknames: Vec<String>,
+    pub favorites: Option<u32>,
+    pub about: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JikanCharacterResponse {
+    pub data: CharacterDetails,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Genre {
+    pub mal_id: u32,
+    pub name: String,
+    pub url: String,
+    pub count: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JikanGenresResponse {
+    pub data: Vec<Genre>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExternalLink {
+    pub name: String,
+    pub url: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JikanExternalLinksResponse {
+    pub data: Vec<ExternalLink>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Producer {
+    pub mal_id: u32,
+    pub url: String,
+    pub titles: Vec<Title>,
+    pub images: Option<CommonImageResource>,
+    pub favorites: Option<u32>,
+    pub count: Option<u32>,
+    pub established: Option<String>,
+    pub about: Option<String>,
+    #[serde(default)]
+    pub external: Vec<ExternalLink>,
+}
+
+impl Producer {
+    // Jikan lists a producer's names by type; the "Default" one is what MAL shows as the name.
+    pub fn name(&self) -> &str {
+        self.titles
+            .iter()
+            .find(|t| t.title_type == "Default")
+            .or_else(|| self.titles.first())
+            .map_or("Unknown", |t| t.title.as_str())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JikanProducersResponse {
+    pub data: Vec<Producer>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JikanProducerResponse {
+    pub data: Producer,
+}
+
+// Shared request helper: every endpoint goes through here so errors are logged the same way.
+async fn fetch_body(url: &str, query: &[(&str, &str)], what: &str) -> Option<String> {
     let client = Client::new();
-    let search_url = "https://api.jikan.moe/v4/anime";
+    jikan_rate_limit::wait().await;
 
-    let res = match client
-        .get(search_url)
-        .query(&[("q", query), ("limit", "5")])
-        .send()
-        .await
-    {
+    let res = match client.get(url).query(query).send().await {
         Ok(response) => response,
         Err(e) => {
-            eprintln!("Error sending anime search request: {}", e);
+            eprintln!("Error sending {} request: {}", what, e);
             return None;
         }
     };
 
     if res.status().is_success() {
-        match res.json::<JikanSearchResponse>().await {
-            Ok(search_results) => Some(search_results.data),
+        match res.text().await {
+            Ok(text) => Some(text),
             Err(e) => {
-                eprintln!("Error converting anime search response to JSON: {}", e);
+                eprintln!("Error reading {} response body: {}", what, e);
                 None
             }
         }
     } else {
-        eprintln!("Error code when searching for anime: {}", res.status());
+        eprintln!("Error code when getting {}: {}", what, res.status());
         eprintln!("Response body: {:?}", res.text().await);
         None
     }
 }
 
-pub async fn get_anime_details_by_id(mal_id: u32) -> Option<AnimeDetails> {
-    let client = Client::new();
-    let details_url = format!("https://api.jikan.moe/v4/anime/{}", mal_id);
-
-    let res = match client.get(&details_url).send().await {
-        Ok(response) => response,
+fn parse_body<T: DeserializeOwned>(body: &str, what: &str) -> Option<T> {
+    match serde_json::from_str::<T>(body) {
+        Ok(parsed) => Some(parsed),
         Err(e) => {
-            eprintln!(
-                "Error sending anime details request for ID {}: {}",
-                mal_id, e
-            );
-            return None;
+            eprintln!("Error converting {} response to JSON: {}", what, e);
+            eprintln!("Problematic {} response body: {}", what, body);
+            None
         }
-    };
+    }
+}
 
-    if res.status().is_success() {
-        let body = match res.text().await {
-            Ok(text) => text,
-            Err(e) => {
-                eprintln!("Error reading response body for ID {}: {}", mal_id, e);
-                return None;
-            }
-        };
+async fn fetch_jikan<T: DeserializeOwned>(
+    url: &str,
+    query: &[(&str, &str)],
+    what: &str,
+) -> Option<T> {
+    let body = fetch_body(url, query, what).await?;
+    parse_body(&body, what)
+}
 
-        match serde_json::from_str::<JikanDetailsResponse>(&body) {
-            Ok(details_response) => Some(details_response.data),
-            Err(e) => {
-                eprintln!(
-                    "Error converting anime details response to JSON for ID {}: {}",
-                    mal_id, e
-                );
-                eprintln!("Problematic response body: {}", body);
-                None
+fn cache_key(url: &str, query: &[(&str, &str)]) -> String {
+    let params: Vec<String> = query.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+    format!("{}?{}", url, params.join("&"))
+}
+
+async fn is_cached(url: &str, query: &[(&str, &str)]) -> bool {
+    let cache = RESPONSE_CACHE.lock().await;
+    cache
+        .get(&cache_key(url, query))
+        .is_some_and(|(stored_at, _)| stored_at.elapsed() < CACHE_TTL)
+}
+
+// Like `fetch_jikan`, but answers from `RESPONSE_CACHE` while the stored body is fresh.
+async fn fetch_jikan_cached<T: DeserializeOwned>(
+    url: &str,
+    query: &[(&str, &str)],
+    what: &str,
+) -> Option<T> {
+    let key = cache_key(url, query);
+    {
+        let cache = RESPONSE_CACHE.lock().await;
+        if let Some((stored_at, body)) = cache.get(&key) {
+            if stored_at.elapsed() < CACHE_TTL {
+                if let Some(parsed) = parse_body(body, what) {
+                    return Some(parsed);
+                }
             }
         }
-    } else {
-        eprintln!(
-            "Error code when getting anime details for ID {}: {}",
-            mal_id,
-            res.status()
-        );
-        eprintln!("Response body: {:?}", res.text().await);
-        None
     }
+
+    let body = fetch_body(url, query, what).await?;
+    let parsed = parse_body(&body, what)?;
+
+    let mut cache = RESPONSE_CACHE.lock().await;
+    cache.retain(|_, (stored_at, _)| stored_at.elapsed() < CACHE_TTL);
+    if cache.len() >= CACHE_CAPACITY {
+        let oldest = cache
+            .iter()
+            .min_by_key(|(_, (stored_at, _))| *stored_at)
+            .map(|(key, _)| key.clone());
+        if let Some(oldest) = oldest {
+            cache.remove(&oldest);
+        }
+    }
+    cache.insert(key, (Instant::now(), body));
+
+    Some(parsed)
+}
+
+// With `sfw` set, Jikan leaves adult entries out of the results.
+pub async fn search_anime(query: &str, sfw: bool) -> Option<Vec<Anime>> {
+    let search_url = format!("{}/anime", JIKAN_BASE_URL);
+    let mut query = vec![("q", query), ("limit", "5")];
+    if sfw {
+        query.push(("sfw", "true"));
+    }
+    fetch_jikan::<JikanSearchResponse>(&search_url, &query, "anime search")
+        .await
+        .map(|response| response.data)
+}
+
+pub async fn get_anime_details_by_id(mal_id: u32) -> Option<AnimeDetails> {
+    let details_url = format!("{}/anime/{}", JIKAN_BASE_URL, mal_id);
+    let what = format!("anime details for ID {}", mal_id);
+    fetch_jikan_cached::<JikanDetailsResponse>(&details_url, &[], &what)
+        .await
+        .map(|response| response.data)
 }
 
-// New function to get anime recommendations
 pub async fn get_anime_recommendations(mal_id: u32) -> Option<Vec<RecommendationItem>> {
-    let client = Client::new();
-    let recommendations_url = format!("https://api.jikan.moe/v4/anime/{}/recommendations", mal_id);
+    let recommendations_url = format!("{}/anime/{}/recommendations", JIKAN_BASE_URL, mal_id);
+    let what = format!("recommendations for ID {}", mal_id);
+    fetch_jikan::<JikanRecommendationsResponse>(&recommendations_url, &[], &what)
+        .await
+        .map(|response| response.data)
+}
 
-    let res = match client.get(&recommendations_url).send().await {
-        Ok(response) => response,
-        Err(e) => {
-            eprintln!(
-                "Error sending recommendations request for ID {}: {}",
-                mal_id, e
-            );
-            return None;
+pub async fn get_random_anime() -> Option<AnimeDetails> {
+    let random_url = format!("{}/random/anime", JIKAN_BASE_URL);
+    fetch_jikan::<JikanDetailsResponse>(&random_url, &[], "random anime")
+        .await
+        .map(|response| response.data)
+}
+
+pub async fn get_manga_details_by_id(mal_id: u32) -> Option<MangaDetails> {
+    let details_url = format!("{}/manga/{}", JIKAN_BASE_URL, mal_id);
+    let what = format!("manga details for ID {}", mal_id);
+    fetch_jikan_cached::<JikanMangaResponse>(&details_url, &[], &what)
+        .await
+        .map(|response| response.data)
+}
+
+pub async fn get_random_manga() -> Option<MangaDetails> {
+    let random_url = format!("{}/random/manga", JIKAN_BASE_URL);
+    fetch_jikan::<JikanMangaResponse>(&random_url, &[], "random manga")
+        .await
+        .map(|response| response.data)
+}
+
+pub async fn get_random_character() -> Option<CharacterDetails> {
+    let random_url = format!("{}/random/characters", JIKAN_BASE_URL);
+    fetch_jikan::<JikanCharacterResponse>(&random_url, &[], "random character")
+        .await
+        .map(|response| response.data)
+}
+
+// `filter` is one of Jikan's genre groups: "genres", "explicit_genres", "themes" or "demographics".
+pub async fn get_anime_genres(filter: Option<&str>) -> Option<Vec<Genre>> {
+    let genres_url = format!("{}/genres/anime", JIKAN_BASE_URL);
+    let query: Vec<(&str, &str)> = filter.map(|f| ("filter", f)).into_iter().collect();
+    fetch_jikan::<JikanGenresResponse>(&genres_url, &query, "anime genres")
+        .await
+        .map(|response| response.data)
+}
+
+pub async fn search_anime_by_genre(genre_id: u32, limit: u32, sfw: bool) -> Option<Vec<Anime>> {
+    let search_url = format!("{}/anime", JIKAN_BASE_URL);
+    let genre_id = genre_id.to_string();
+    let limit = limit.to_string();
+    let mut query = vec![
+        ("genres", genre_id.as_str()),
+        ("order_by", "score"),
+        ("sort", "desc"),
+        ("limit", limit.as_str()),
+    ];
+    if sfw {
+        query.push(("sfw", "true"));
+    }
+    fetch_jikan::<JikanSearchResponse>(&search_url, &query, "anime genre search")
+        .await
+        .map(|response| response.data)
+}
+
+pub async fn search_producers(query: &str) -> Option<Vec<Producer>> {
+    let search_url = format!("{}/producers", JIKAN_BASE_URL);
+    let query = [
+        ("q", query),
+        ("order_by", "count"),
+        ("sort", "desc"),
+        ("limit", "10"),
+    ];
+    fetch_jikan::<JikanProducersResponse>(&search_url, &query, "producer search")
+        .await
+        .map(|response| response.data)
+}
+
+pub async fn get_producer_full(producer_id: u32) -> Option<Producer> {
+    let producer_url = format!("{}/producers/{}/full", JIKAN_BASE_URL, producer_id);
+    let what = format!("producer details for ID {}", producer_id);
+    fetch_jikan::<JikanProducerResponse>(&producer_url, &[], &what)
+        .await
+        .map(|response| response.data)
+}
+
+pub async fn search_anime_by_producer(
+    producer_id: u32,
+    page: u32,
+    limit: u32,
+    sfw: bool,
+) -> Option<JikanSearchResponse> {
+    let search_url = format!("{}/anime", JIKAN_BASE_URL);
+    let producer_id = producer_id.to_string();
+    let page = page.to_string();
+    let limit = limit.to_string();
+    let mut query = vec![
+        ("producers", producer_id.as_str()),
+        ("order_by", "score"),
+        ("sort", "desc"),
+        ("page", page.as_str()),
+        ("limit", limit.as_str()),
+    ];
+    if sfw {
+        query.push(("sfw", "true"));
+    }
+    fetch_jikan::<JikanSearchResponse>(&search_url, &query, "anime producer search").await
+}
+
+pub async fn get_anime_full(mal_id: u32) -> Option<AnimeDetails> {
+    let full_url = format!("{}/anime/{}/full", JIKAN_BASE_URL, mal_id);
+    let what = format!("full anime details for ID {}", mal_id);
+    fetch_jikan_cached::<JikanDetailsResponse>(&full_url, &[], &what)
+        .await
+        .map(|response| response.data)
+}
+
+// Jikan pages episodes 100 at a time; `page` starts at 1.
+pub async fn get_anime_episodes(mal_id: u32, page: u32) -> Option<JikanEpisodesResponse> {
+    let episodes_url = format!("{}/anime/{}/episodes", JIKAN_BASE_URL, mal_id);
+    let page = page.to_string();
+    let what = format!("episodes for ID {}", mal_id);
+    fetch_jikan_cached::<JikanEpisodesResponse>(&episodes_url, &[("page", page.as_str())], &what)
+        .await
+}
+
+pub async fn get_anime_episode(mal_id: u32, episode: u32) -> Option<EpisodeDetails> {
+    let episode_url = format!("{}/anime/{}/episodes/{}", JIKAN_BASE_URL, mal_id, episode);
+    let what = format!("episode {} for ID {}", episode, mal_id);
+    fetch_jikan_cached::<JikanEpisodeResponse>(&episode_url, &[], &what)
+        .await
+        .map(|response| response.data)
+}
+
+pub async fn get_anime_streaming(mal_id: u32) -> Option<Vec<ExternalLink>> {
+    let streaming_url = format!("{}/anime/{}/streaming", JIKAN_BASE_URL, mal_id);
+    let what = format!("streaming links for ID {}", mal_id);
+    fetch_jikan_cached::<JikanExternalLinksResponse>(&streaming_url, &[], &what)
+        .await
+        .map(|response| response.data)
+}
+
+pub async fn get_anime_external_links(mal_id: u32) -> Option<Vec<ExternalLink>> {
+    let external_url = format!("{}/anime/{}/external", JIKAN_BASE_URL, mal_id);
+    let what = format!("external links for ID {}", mal_id);
+    fetch_jikan_cached::<JikanExternalLinksResponse>(&external_url, &[], &what)
+        .await
+        .map(|response| response.data)
+}
+
+pub async fn get_anime_statistics(mal_id: u32) -> Option<AnimeStatistics> {
+    let statistics_url = format!("{}/anime/{}/statistics", JIKAN_BASE_URL, mal_id);
+    let what = format!("statistics for ID {}", mal_id);
+    fetch_jikan_cached::<JikanStatisticsResponse>(&statistics_url, &[], &what)
+        .await
+        .map(|response| response.data)
+}
+
+// Jikan pages reviews 20 at a time; spoiler and preliminary reviews are included so the bot can mark them.
+pub async fn get_anime_reviews(mal_id: u32, page: u32) -> Option<JikanReviewsResponse> {
+    let reviews_url = format!("{}/anime/{}/reviews", JIKAN_BASE_URL, mal_id);
+    let page = page.to_string();
+    let query = [
+        ("page", page.as_str()),
+        ("spoilers", "true"),
+        ("preliminary", "true"),
+    ];
+    let what = format!("reviews for ID {}", mal_id);
+    fetch_jikan_cached::<JikanReviewsResponse>(&reviews_url, &query, &what).await
+}
+
+// Not cached: the news poller needs to see new articles as soon as Jikan has them.
+pub async fn get_anime_news(mal_id: u32) -> Option<Vec<NewsArticle>> {
+    let news_url = format!("{}/anime/{}/news", JIKAN_BASE_URL, mal_id);
+    let what = format!("news for ID {}", mal_id);
+    fetch_jikan::<JikanNewsResponse>(&news_url, &[], &what)
+        .await
+        .map(|response| response.data)
+}
+
+pub async fn get_anime_pictures(mal_id: u32) -> Option<Vec<CommonImageResource>> {
+    let pictures_url = format!("{}/anime/{}/pictures", JIKAN_BASE_URL, mal_id);
+    let what = format!("pictures for ID {}", mal_id);
+    fetch_jikan_cached::<JikanPicturesResponse>(&pictures_url, &[], &what)
+        .await
+        .map(|response| response.data)
+}
+
+pub async fn get_anime_videos(mal_id: u32) -> Option<AnimeVideos> {
+    let videos_url = format!("{}/anime/{}/videos", JIKAN_BASE_URL, mal_id);
+    let what = format!("videos for ID {}", mal_id);
+    fetch_jikan_cached::<JikanVideosResponse>(&videos_url, &[], &what)
+        .await
+        .map(|response| response.data)
+}
+
+pub async fn get_anime_themes(mal_id: u32) -> Option<AnimeThemes> {
+    let themes_url = format!("{}/anime/{}/themes", JIKAN_BASE_URL, mal_id);
+    let what = format!("themes for ID {}", mal_id);
+    fetch_jikan_cached::<JikanThemesResponse>(&themes_url, &[], &what)
+        .await
+        .map(|response| response.data)
+}
+
+pub async fn get_user_profile(username: &str) -> Option<UserProfile> {
+    let profile_url = format!("{}/users/{}/full", JIKAN_BASE_URL, username);
+    let what = format!("user profile for {}", username);
+    fetch_jikan_cached::<JikanUserResponse>(&profile_url, &[], &what)
+        .await
+        .map(|response| response.data)
+}
+
+pub async fn get_anime_relations(mal_id: u32) -> Option<Vec<Relation>> {
+    let relations_url = format!("{}/anime/{}/relations", JIKAN_BASE_URL, mal_id);
+    let what = format!("relations for ID {}", mal_id);
+    fetch_jikan_cached::<JikanRelationsResponse>(&relations_url, &[], &what)
+        .await
+        .map(|response| response.data)
+}
+
+// Breadth-first walk over an anime's relation graph. Cached entries are free; every other
+// lookup spends one request from `request_budget`. Returns `None` only if the root itself fails.
+pub async fn walk_franchise(
+    root_id: u32,
+    max_depth: usize,
+    request_budget: usize,
+) -> Option<FranchiseWalk> {
+    let mut visited = HashSet::from([root_id]);
+    let mut queue = VecDeque::from([(root_id, 0)]);
+    let mut entries: Vec<AnimeDetails> = Vec::new();
+    let mut budget = request_budget;
+    let mut incomplete = false;
+
+    while let Some((mal_id, depth)) = queue.pop_front() {
+        let full_url = format!("{}/anime/{}/full", JIKAN_BASE_URL, mal_id);
+        if !is_cached(&full_url, &[]).await {
+            if budget == 0 {
+                incomplete = true;
+                break;
+            }
+            budget -= 1;
         }
-    };
 
-    if res.status().is_success() {
-        let body = match res.text().await {
-            Ok(text) => text,
-            Err(e) => {
-                eprintln!(
-                    "Error reading recommendations response body for ID {}: {}",
-                    mal_id, e
-                );
-                return None;
+        let details = match get_anime_full(mal_id).await {
+            Some(details) => details,
+            None if entries.is_empty() => return None,
+            None => {
+                incomplete = true;
+                continue;
             }
         };
 
-        match serde_json::from_str::<JikanRecommendationsResponse>(&body) {
-            Ok(recommendations_response) => Some(recommendations_response.data),
-            Err(e) => {
-                eprintln!(
-                    "Error converting recommendations response to JSON for ID {}: {}",
-                    mal_id, e
-                );
-                eprintln!("Problematic recommendations response body: {}", body);
-                None
+        let related_ids = details
+            .relations
+            .iter()
+            .filter(|r| !WATCH_ORDER_SKIPPED_RELATIONS.contains(&r.relation.as_str()))
+            .flat_map(|r| r.entry.iter())
+            .filter(|e| e.entry_type == "anime")
+            .map(|e| e.mal_id);
+        for related_id in related_ids {
+            if visited.contains(&related_id) {
+                continue;
+            }
+            if depth >= max_depth {
+                incomplete = true;
+                continue;
             }
+            visited.insert(related_id);
+            queue.push_back((related_id, depth + 1));
         }
-    } else {
-        eprintln!(
-            "Error code when getting recommendations for ID {}: {}",
-            mal_id,
-            res.status()
-        );
-        eprintln!("Response body: {:?}", res.text().await);
-        None
+
+        entries.push(details);
     }
+
+    // Entries without an air date (usually unannounced sequels) go last.
+    entries.sort_by(|a, b| {
+        (a.aired.from.is_none(), &a.aired.from).cmp(&(b.aired.from.is_none(), &b.aired.from))
+    });
+
+    Some(FranchiseWalk {
+        entries,
+        incomplete,
+    })
 }
@@ -0,0 +1,75 @@
+// Client-side throttle for the Jikan API, shared by every command and background task so the bot
+// stays within Jikan's limits instead of running into 429 responses.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+// Jikan's documented limits: 3 requests per second and 60 requests per minute.
+const RATE_LIMITS: [(usize, Duration); 2] =
+    [(3, Duration::from_secs(1)), (60, Duration::from_secs(60))];
+
+// Send times of the most recent requests, oldest first.
+static SENT_REQUESTS: Mutex<VecDeque<Instant>> = Mutex::const_new(VecDeque::new());
+
+// When the next request may go out without breaking any window in `RATE_LIMITS`, or `None` if it
+// may go out right away.
+fn ready_at(sent: &VecDeque<Instant>, now: Instant) -> Option<Instant> {
+    RATE_LIMITS
+        .iter()
+        .filter(|(count, _)| sent.len() >= *count)
+        .map(|(count, window)| sent[sent.len() - count] + *window)
+        .filter(|ready_at| *ready_at > now)
+        .max()
+}
+
+// Records a request sent at `now`, keeping only as many send times as the largest window needs.
+fn record(sent: &mut VecDeque<Instant>, now: Instant) {
+    sent.push_back(now);
+    let keep = RATE_LIMITS
+        .iter()
+        .map(|(count, _)| *count)
+        .max()
+        .unwrap_or(0);
+    while sent.len() > keep {
+        sent.pop_front();
+    }
+}
+
+// Waits until sending another request stays within every window in `RATE_LIMITS`, then records it.
+pub async fn wait() {
+    let mut sent = SENT_REQUESTS.lock().await;
+    while let Some(ready_at) = ready_at(&sent, Instant::now()) {
+        tokio::time::sleep_until(ready_at).await;
+    }
+    record(&mut sent, Instant::now());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests_wait_for_the_per_second_and_per_minute_windows() {
+        let start = Instant::now();
+        let mut sent = VecDeque::new();
+        for _ in 0..3 {
+            assert_eq!(ready_at(&sent, start), None);
+            record(&mut sent, start);
+        }
+        // The fourth request in the same second waits for the oldest one to leave the window.
+        assert_eq!(ready_at(&sent, start), Some(start + Duration::from_secs(1)));
+
+        // Three requests a second fill the minute after 20 seconds.
+        let mut sent = VecDeque::new();
+        for second in 0..20 {
+            for _ in 0..3 {
+                record(&mut sent, start + Duration::from_secs(second));
+            }
+        }
+        let now = start + Duration::from_secs(20);
+        assert_eq!(ready_at(&sent, now), Some(start + Duration::from_secs(60)));
+        assert_eq!(sent.len(), 60);
+    }
+}
@@ -4,8 +4,18 @@ use std::env;
 use serenity::prelude::*; // Presence da burada kullanılmadığı için kaldırılabilir, ancak örneğinizde vardı.
 
 // Yeni modülleri tanımlıyoruz
+mod chart;
+mod commands;
 mod discord_handler;
+mod embed;
+mod guild_settings;
+mod i18n;
 mod jikan_api; // Handler struct'ı burada
+mod jikan_rate_limit;
+mod news_feed;
+mod rate_limit;
+mod taste;
+mod user_settings;
 
 #[tokio::main]
 async fn main() {
@@ -0,0 +1,228 @@
+use serenity::builder::{CreateEmbed, CreateEmbedFooter, CreateMessage};
+use serenity::http::Http;
+use serenity::model::id::{ChannelId, GuildId};
+use serenity::model::Timestamp;
+
+use crate::embed;
+use crate::guild_settings;
+use crate::i18n::Locale;
+use crate::jikan_api::{self, NewsArticle};
+use serde::{Deserialize, Serialize};
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::fs;
+use tokio::sync::Mutex;
+
+const SUBSCRIPTIONS_FILE: &str = "data/news_subscriptions.json";
+const POLL_INTERVAL: Duration = Duration::from_secs(30 * 60);
+// Only the most recent article IDs per anime are remembered; Jikan's news list is much shorter than this.
+const MAX_REMEMBERED_ARTICLES: usize = 100;
+
+// Serializes every read-modify-write of the subscriptions file between commands and the poller.
+static STORE_LOCK: Mutex<()> = Mutex::const_new(());
+// `ready` fires again after reconnects; only the first one may start the poller.
+static POLLER_STARTED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct NewsSubscriptions {
+    // Keyed by channel ID.
+    pub channels: BTreeMap<String, ChannelSubscription>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ChannelSubscription {
+    pub guild_id: String,
+    // Subscribed MAL IDs mapped to the IDs of the articles already posted for them.
+    pub posted: BTreeMap<u32, Vec<u32>>,
+}
+
+async fn load_subscriptions() -> NewsSubscriptions {
+    match fs::read_to_string(SUBSCRIPTIONS_FILE).await {
+        Ok(data) => serde_json::from_str(&data).unwrap_or_default(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => NewsSubscriptions::default(),
+        Err(e) => {
+            eprintln!("Error loading news subscriptions file: {}", e);
+            NewsSubscriptions::default()
+        }
+    }
+}
+
+async fn save_subscriptions(subscriptions: &NewsSubscriptions) {
+    let json_data = serde_json::to_string_pretty(subscriptions)
+        .expect("Failed to serialize news subscriptions");
+    if let Err(dir_err) = fs::create_dir_all("data").await {
+        eprintln!("Error creating 'data' directory before saving: {}", dir_err);
+        return;
+    }
+    if let Err(e) = fs::write(SUBSCRIPTIONS_FILE, json_data).await {
+        eprintln!("Error saving news subscriptions to file: {}", e);
+    }
+}
+
+fn remember(posted: &mut Vec<u32>, article_id: u32) {
+    if !posted.contains(&article_id) {
+        posted.push(article_id);
+    }
+    if posted.len() > MAX_REMEMBERED_ARTICLES {
+        posted.drain(..posted.len() - MAX_REMEMBERED_ARTICLES);
+    }
+}
+
+pub fn news_article_embed(article: &NewsArticle, mal_id: u32, locale: Locale) -> CreateEmbed {
+    let mut embed = CreateEmbed::new()
+        .title(&article.title)
+        .url(&article.url)
+        .footer(CreateEmbedFooter::new(
+            locale.t_args("news-feed-footer", &[("id", mal_id.into())]),
+        ))
+        .timestamp(Timestamp::now());
+
+    if let Some(excerpt) = &article.excerpt {
+        embed = embed.description(excerpt);
+    }
+    if let Some(image_url) = article.images.as_ref().and_then(|i| i.largest_url()) {
+        embed = embed.thumbnail(image_url);
+    }
+    if let Some(author) = &article.author_username {
+        embed = embed.field(locale.t("field-author"), author, true);
+    }
+    embed = embed.field(
+        locale.t("field-date"),
+        article.date.get(..10).unwrap_or(&article.date),
+        true,
+    );
+    if let Some(comments) = article.comments {
+        embed = embed.field(locale.t("field-comments"), comments.to_string(), true);
+    }
+    embed
+}
+
+// Subscribes a channel to news for the given anime. Articles that already exist are marked as
+// posted, so only news published after subscribing shows up. Returns the IDs that were added.
+pub async fn subscribe(guild_id: String, channel_id: String, mal_ids: &[u32]) -> Vec<u32> {
+    // Fetch outside the lock: the rate limiter may make this take a while.
+    let mut existing: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+    for &mal_id in mal_ids {
+        let articles = jikan_api::get_anime_news(mal_id).await.unwrap_or_default();
+        existing.insert(mal_id, articles.iter().map(|a| a.mal_id).collect());
+    }
+
+    let _guard = STORE_LOCK.lock().await;
+    let mut subscriptions = load_subscriptions().await;
+    let channel = subscriptions.channels.entry(channel_id).or_default();
+    channel.guild_id = guild_id;
+
+    let mut added = Vec::new();
+    for (mal_id, article_ids) in existing {
+        if let Entry::Vacant(entry) = channel.posted.entry(mal_id) {
+            entry.insert(article_ids);
+            added.push(mal_id);
+        }
+    }
+    save_subscriptions(&subscriptions).await;
+    added
+}
+
+// Removes the given anime from a channel's subscription, or all of them when `mal_ids` is `None`.
+// Returns how many subscriptions were removed.
+pub async fn unsubscribe(channel_id: &str, mal_ids: Option<&[u32]>) -> usize {
+    let _guard = STORE_LOCK.lock().await;
+    let mut subscriptions = load_subscriptions().await;
+    let Some(channel) = subscriptions.channels.get_mut(channel_id) else {
+        return 0;
+    };
+
+    let before = channel.posted.len();
+    match mal_ids {
+        Some(mal_ids) => channel.posted.retain(|mal_id, _| !mal_ids.contains(mal_id)),
+        None => channel.posted.clear(),
+    }
+    let removed = before - channel.posted.len();
+    if channel.posted.is_empty() {
+        subscriptions.channels.remove(channel_id);
+    }
+    save_subscriptions(&subscriptions).await;
+    removed
+}
+
+// Subscribed MAL IDs per channel of one guild.
+pub async fn guild_subscriptions(guild_id: &str) -> Vec<(String, Vec<u32>)> {
+    let _guard = STORE_LOCK.lock().await;
+    let subscriptions = load_subscriptions().await;
+    subscriptions
+        .channels
+        .into_iter()
+        .filter(|(_, channel)| channel.guild_id == guild_id)
+        .map(|(channel_id, channel)| (channel_id, channel.posted.into_keys().collect()))
+        .collect()
+}
+
+async fn poll_once(http: &Http) {
+    let mal_ids: BTreeSet<u32> = {
+        let _guard = STORE_LOCK.lock().await;
+        let subscriptions = load_subscriptions().await;
+        subscriptions
+            .channels
+            .values()
+            .flat_map(|channel| channel.posted.keys().copied())
+            .collect()
+    };
+
+    // Every anime is fetched once per round, however many channels follow it. The requests
+    // go through the shared Jikan rate limiter, so commands keep working while this runs.
+    let mut news: BTreeMap<u32, Vec<NewsArticle>> = BTreeMap::new();
+    for mal_id in mal_ids {
+        if let Some(articles) = jikan_api::get_anime_news(mal_id).await {
+            news.insert(mal_id, articles);
+        }
+    }
+
+    let _guard = STORE_LOCK.lock().await;
+    let mut subscriptions = load_subscriptions().await;
+    for (channel_id, channel) in subscriptions.channels.iter_mut() {
+        let Ok(channel_id) = channel_id.parse::<u64>() else {
+            continue;
+        };
+        let channel_id = ChannelId::new(channel_id);
+        let guild_id = channel.guild_id.parse::<u64>().ok().filter(|id| *id != 0);
+        let settings = guild_settings::get(guild_id.map(GuildId::new)).await;
+
+        for (mal_id, posted) in channel.posted.iter_mut() {
+            let Some(articles) = news.get(mal_id) else {
+                continue;
+            };
+            // Jikan lists newest first; post oldest first so the channel reads chronologically.
+            let unposted: Vec<&NewsArticle> = articles
+                .iter()
+                .rev()
+                .filter(|a| !posted.contains(&a.mal_id))
+                .collect();
+            for article in unposted {
+                // Posted to the whole channel, so the server's language rather than anyone's own.
+                let embed = news_article_embed(article, *mal_id, settings.language);
+                let builder = CreateMessage::new().embed(embed::fit(settings.style(embed)));
+                match channel_id.send_message(http, builder).await {
+                    Ok(_) => remember(posted, article.mal_id),
+                    Err(why) => println!("Error posting news to channel {}: {:?}", channel_id, why),
+                }
+            }
+        }
+    }
+    save_subscriptions(&subscriptions).await;
+}
+
+pub fn start_poller(http: Arc<Http>) {
+    if POLLER_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            poll_once(&http).await;
+        }
+    });
+}
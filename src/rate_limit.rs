@@ -0,0 +1,212 @@
+// Anti-spam limits checked before a command runs: cooldowns per user and per server, a cap on how
+// many commands one user can have running at once, and a limit on how often the bot complains
+// about either so the warnings don't turn into spam themselves.
+
+use std::collections::BTreeMap;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+// How many commands one user can have running at the same time.
+const MAX_RUNNING_PER_USER: usize = 2;
+// A user gets at most one warning per interval; other attempts in between are ignored silently.
+const WARNING_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Scope {
+    User,
+    Guild,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Limited {
+    // The command is on cooldown for the user or the whole server, for this much longer.
+    Cooldown(Scope, Duration),
+    // The user already has `MAX_RUNNING_PER_USER` commands running.
+    Busy,
+}
+
+struct Limiter {
+    // When each cooldown ends, by scope, user or guild ID and command name.
+    cooldowns: BTreeMap<(Scope, u64, &'static str), Instant>,
+    // Commands each user has running.
+    running: BTreeMap<u64, usize>,
+    // When each user was last warned.
+    warned: BTreeMap<u64, Instant>,
+}
+
+impl Limiter {
+    const fn new() -> Self {
+        Limiter {
+            cooldowns: BTreeMap::new(),
+            running: BTreeMap::new(),
+            warned: BTreeMap::new(),
+        }
+    }
+
+    fn start(
+        &mut self,
+        user_id: u64,
+        guild_id: Option<u64>,
+        command: &'static str,
+        cooldowns: (Duration, Duration),
+        now: Instant,
+    ) -> Result<(), Limited> {
+        if self.running.get(&user_id).copied().unwrap_or(0) >= MAX_RUNNING_PER_USER {
+            return Err(Limited::Busy);
+        }
+
+        self.cooldowns.retain(|_, until| *until > now);
+        let (user_cooldown, guild_cooldown) = cooldowns;
+        let mut keys = vec![((Scope::User, user_id, command), user_cooldown)];
+        if let Some(guild_id) = guild_id {
+            keys.push(((Scope::Guild, guild_id, command), guild_cooldown));
+        }
+        keys.retain(|(_, cooldown)| !cooldown.is_zero());
+        for (key, _) in &keys {
+            if let Some(until) = self.cooldowns.get(key) {
+                return Err(Limited::Cooldown(key.0, *until - now));
+            }
+        }
+
+        for (key, cooldown) in keys {
+            self.cooldowns.insert(key, now + cooldown);
+        }
+        *self.running.entry(user_id).or_default() += 1;
+        Ok(())
+    }
+
+    fn finish(&mut self, user_id: u64) {
+        if let Some(count) = self.running.get_mut(&user_id) {
+            *count -= 1;
+            if *count == 0 {
+                self.running.remove(&user_id);
+            }
+        }
+    }
+
+    fn should_warn(&mut self, user_id: u64, now: Instant) -> bool {
+        self.warned
+            .retain(|_, warned| now.duration_since(*warned) < WARNING_INTERVAL);
+        if self.warned.contains_key(&user_id) {
+            return false;
+        }
+        self.warned.insert(user_id, now);
+        true
+    }
+}
+
+static LIMITER: Mutex<Limiter> = Mutex::new(Limiter::new());
+
+fn limiter() -> MutexGuard<'static, Limiter> {
+    LIMITER
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// Held while a command runs; dropping it frees the user's slot.
+pub struct Running {
+    user_id: u64,
+}
+
+impl Drop for Running {
+    fn drop(&mut self) {
+        limiter().finish(self.user_id);
+    }
+}
+
+// Records a use of `command` with its `(per user, per server)` cooldowns, or says which limit
+// stops it. Direct messages only have the per-user cooldown.
+pub fn start(
+    user_id: u64,
+    guild_id: Option<u64>,
+    command: &'static str,
+    cooldowns: (Duration, Duration),
+) -> Result<Running, Limited> {
+    limiter().start(user_id, guild_id, command, cooldowns, Instant::now())?;
+    Ok(Running { user_id })
+}
+
+// Whole seconds left on a cooldown for the warning message, rounded up so it never reads zero
+// while a fraction remains.
+pub fn seconds_left(wait: Duration) -> u64 {
+    wait.as_millis().div_ceil(1000) as u64
+}
+
+// Whether to tell the user they hit a limit, at most once per `WARNING_INTERVAL`.
+pub fn should_warn(user_id: u64) -> bool {
+    limiter().should_warn(user_id, Instant::now())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const fn seconds(secs: u64) -> Duration {
+        Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn user_and_guild_cooldowns_are_separate() {
+        let mut limiter = Limiter::new();
+        let now = Instant::now();
+        let cooldowns = (seconds(5), seconds(2));
+        assert!(limiter.start(1, Some(10), "genre", cooldowns, now).is_ok());
+        limiter.finish(1);
+
+        // Another user on the same server waits for the server cooldown only.
+        assert_eq!(
+            limiter.start(2, Some(10), "genre", cooldowns, now + seconds(1)),
+            Err(Limited::Cooldown(Scope::Guild, seconds(1)))
+        );
+        assert!(limiter
+            .start(2, Some(10), "genre", cooldowns, now + seconds(2))
+            .is_ok());
+        limiter.finish(2);
+
+        // The first user still waits for their own cooldown, on any server.
+        assert_eq!(
+            limiter.start(1, Some(20), "genre", cooldowns, now + seconds(3)),
+            Err(Limited::Cooldown(Scope::User, seconds(2)))
+        );
+        assert!(limiter.start(1, None, "genres", cooldowns, now).is_ok());
+        limiter.finish(1);
+        assert!(limiter
+            .start(1, Some(20), "genre", cooldowns, now + seconds(5))
+            .is_ok());
+    }
+
+    #[test]
+    fn running_commands_are_capped_per_user() {
+        let mut limiter = Limiter::new();
+        let now = Instant::now();
+        let none = (Duration::ZERO, Duration::ZERO);
+        for _ in 0..MAX_RUNNING_PER_USER {
+            assert!(limiter.start(1, None, "help", none, now).is_ok());
+        }
+        assert_eq!(
+            limiter.start(1, None, "help", none, now),
+            Err(Limited::Busy)
+        );
+        assert!(limiter.start(2, None, "help", none, now).is_ok());
+        limiter.finish(1);
+        assert!(limiter.start(1, None, "help", none, now).is_ok());
+    }
+
+    #[test]
+    fn seconds_left_rounds_up_partial_seconds_only() {
+        assert_eq!(seconds_left(seconds(3)), 3);
+        assert_eq!(seconds_left(Duration::from_millis(2_001)), 3);
+        assert_eq!(seconds_left(Duration::from_millis(1)), 1);
+        assert_eq!(seconds_left(Duration::ZERO), 0);
+    }
+
+    #[test]
+    fn warnings_are_rate_limited() {
+        let mut limiter = Limiter::new();
+        let now = Instant::now();
+        assert!(limiter.should_warn(1, now));
+        assert!(!limiter.should_warn(1, now + seconds(1)));
+        assert!(limiter.should_warn(2, now + seconds(1)));
+        assert!(limiter.should_warn(1, now + WARNING_INTERVAL));
+    }
+}
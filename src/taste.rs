@@ -0,0 +1,247 @@
+// Taste comparison behind `!mal compare`. Everything here works on plain IDs and numbers so the
+// scoring can be tested without Discord or Jikan.
+
+use std::collections::HashSet;
+
+// Weights of the three signals in the compatibility score. Components a profile can't provide
+// (e.g. nobody has favorites) are left out and the rest are scaled back up to 100%.
+const FAVORITES_WEIGHT: f64 = 0.5;
+const MEAN_SCORE_WEIGHT: f64 = 0.25;
+const STATUS_MIX_WEIGHT: f64 = 0.25;
+
+// Two mean scores this far apart (or further) count as having nothing in common.
+const MEAN_SCORE_TOLERANCE: f64 = 3.0;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TasteProfile {
+    pub favorite_anime: Vec<u32>,
+    pub favorite_manga: Vec<u32>,
+    pub favorite_characters: Vec<u32>,
+    // `None` when the user hasn't scored anything; MAL reports those as a mean of 0.
+    pub anime_mean_score: Option<f64>,
+    // Watching, completed, on hold, dropped and plan to watch counts, in that order.
+    pub anime_status_counts: [u32; 5],
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Overlap {
+    pub shared: Vec<u32>,
+    pub only_first: Vec<u32>,
+    pub only_second: Vec<u32>,
+}
+
+impl Overlap {
+    // Sørensen–Dice coefficient: shared entries relative to the average list size. `None` when
+    // both lists are empty, since there is nothing to compare.
+    pub fn similarity(&self) -> Option<f64> {
+        let total = 2 * self.shared.len() + self.only_first.len() + self.only_second.len();
+        if total == 0 {
+            return None;
+        }
+        Some((2 * self.shared.len()) as f64 / total as f64)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Compatibility {
+    // 0 to 100, or `None` when neither profile has anything to compare.
+    pub score: Option<u8>,
+    pub anime: Overlap,
+    pub manga: Overlap,
+    pub characters: Overlap,
+}
+
+// Splits two ID lists into shared and one-sided entries, keeping each list's own order and
+// dropping duplicates.
+pub fn overlap(first: &[u32], second: &[u32]) -> Overlap {
+    let first_set: HashSet<u32> = first.iter().copied().collect();
+    let second_set: HashSet<u32> = second.iter().copied().collect();
+    let mut seen = HashSet::new();
+    let mut result = Overlap::default();
+
+    for &id in first {
+        if !seen.insert(id) {
+            continue;
+        }
+        if second_set.contains(&id) {
+            result.shared.push(id);
+        } else {
+            result.only_first.push(id);
+        }
+    }
+    for &id in second {
+        if seen.insert(id) && !first_set.contains(&id) {
+            result.only_second.push(id);
+        }
+    }
+    result
+}
+
+fn mean_score_similarity(first: Option<f64>, second: Option<f64>) -> Option<f64> {
+    let difference = (first? - second?).abs();
+    Some((1.0 - difference / MEAN_SCORE_TOLERANCE).max(0.0))
+}
+
+// Cosine similarity of the two status distributions, so list size doesn't matter, only how
+// each user splits their list between watching, completing, dropping and so on.
+fn status_mix_similarity(first: &[u32; 5], second: &[u32; 5]) -> Option<f64> {
+    let dot: f64 = first
+        .iter()
+        .zip(second)
+        .map(|(a, b)| *a as f64 * *b as f64)
+        .sum();
+    let norm = |counts: &[u32; 5]| {
+        counts
+            .iter()
+            .map(|c| (*c as f64).powi(2))
+            .sum::<f64>()
+            .sqrt()
+    };
+    let norms = norm(first) * norm(second);
+    if norms == 0.0 {
+        return None;
+    }
+    Some(dot / norms)
+}
+
+pub fn compatibility(first: &TasteProfile, second: &TasteProfile) -> Compatibility {
+    let anime = overlap(&first.favorite_anime, &second.favorite_anime);
+    let manga = overlap(&first.favorite_manga, &second.favorite_manga);
+    let characters = overlap(&first.favorite_characters, &second.favorite_characters);
+
+    // All favorites are pooled so a shared manga counts as much as a shared anime.
+    let favorites = Overlap {
+        shared: [
+            anime.shared.as_slice(),
+            manga.shared.as_slice(),
+            characters.shared.as_slice(),
+        ]
+        .concat(),
+        only_first: [
+            anime.only_first.as_slice(),
+            manga.only_first.as_slice(),
+            characters.only_first.as_slice(),
+        ]
+        .concat(),
+        only_second: [
+            anime.only_second.as_slice(),
+            manga.only_second.as_slice(),
+            characters.only_second.as_slice(),
+        ]
+        .concat(),
+    };
+
+    let components = [
+        (favorites.similarity(), FAVORITES_WEIGHT),
+        (
+            mean_score_similarity(first.anime_mean_score, second.anime_mean_score),
+            MEAN_SCORE_WEIGHT,
+        ),
+        (
+            status_mix_similarity(&first.anime_status_counts, &second.anime_status_counts),
+            STATUS_MIX_WEIGHT,
+        ),
+    ];
+    let (weighted, total_weight) = components
+        .iter()
+        .filter_map(|(similarity, weight)| similarity.map(|s| (s * weight, *weight)))
+        .fold((0.0, 0.0), |(sum, weights), (value, weight)| {
+            (sum + value, weights + weight)
+        });
+
+    let score = if total_weight > 0.0 {
+        Some((weighted / total_weight * 100.0).round().clamp(0.0, 100.0) as u8)
+    } else {
+        None
+    };
+
+    Compatibility {
+        score,
+        anime,
+        manga,
+        characters,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(favorite_anime: &[u32], mean: Option<f64>, statuses: [u32; 5]) -> TasteProfile {
+        TasteProfile {
+            favorite_anime: favorite_anime.to_vec(),
+            anime_mean_score: mean,
+            anime_status_counts: statuses,
+            ..TasteProfile::default()
+        }
+    }
+
+    #[test]
+    fn overlap_keeps_order_and_drops_duplicates() {
+        let result = overlap(&[3, 1, 2, 1], &[2, 5, 3, 5]);
+        assert_eq!(result.shared, vec![3, 2]);
+        assert_eq!(result.only_first, vec![1]);
+        assert_eq!(result.only_second, vec![5]);
+    }
+
+    #[test]
+    fn similarity_of_empty_lists_is_unknown() {
+        assert_eq!(overlap(&[], &[]).similarity(), None);
+        assert_eq!(overlap(&[1], &[]).similarity(), Some(0.0));
+        assert_eq!(overlap(&[1, 2], &[2, 1]).similarity(), Some(1.0));
+        assert_eq!(overlap(&[1, 2], &[2, 3]).similarity(), Some(0.5));
+    }
+
+    #[test]
+    fn identical_profiles_are_fully_compatible() {
+        let user = profile(&[1, 2, 3], Some(7.5), [2, 100, 5, 3, 40]);
+        assert_eq!(compatibility(&user, &user.clone()).score, Some(100));
+    }
+
+    #[test]
+    fn opposite_profiles_score_zero() {
+        let first = profile(&[1, 2], Some(9.5), [0, 10, 0, 0, 0]);
+        let second = profile(&[3, 4], Some(5.0), [0, 0, 0, 10, 0]);
+        assert_eq!(compatibility(&first, &second).score, Some(0));
+    }
+
+    #[test]
+    fn missing_components_are_left_out_of_the_score() {
+        // No favorites and no scores: only the status mix counts, and it matches perfectly.
+        let first = profile(&[], None, [1, 2, 0, 0, 1]);
+        let second = profile(&[], None, [2, 4, 0, 0, 2]);
+        assert_eq!(compatibility(&first, &second).score, Some(100));
+
+        let empty = TasteProfile::default();
+        assert_eq!(compatibility(&empty, &empty).score, None);
+    }
+
+    #[test]
+    fn score_weights_favorites_half() {
+        // Same mean and status mix, half the favorites shared: 0.5 * 0.5 + 0.25 + 0.25.
+        let first = profile(&[1, 2], Some(8.0), [1, 1, 1, 1, 1]);
+        let second = profile(&[2, 3], Some(8.0), [1, 1, 1, 1, 1]);
+        assert_eq!(compatibility(&first, &second).score, Some(75));
+    }
+
+    #[test]
+    fn favorites_from_every_category_are_pooled() {
+        let first = TasteProfile {
+            favorite_anime: vec![1],
+            favorite_manga: vec![10],
+            favorite_characters: vec![100, 101],
+            ..TasteProfile::default()
+        };
+        let second = TasteProfile {
+            favorite_anime: vec![1],
+            favorite_manga: vec![11],
+            favorite_characters: vec![101],
+            ..TasteProfile::default()
+        };
+        let result = compatibility(&first, &second);
+        assert_eq!(result.manga.only_second, vec![11]);
+        assert_eq!(result.characters.shared, vec![101]);
+        // 2 shared out of 4 + 3 favorites: 4 / 7.
+        assert_eq!(result.score, Some(57));
+    }
+}
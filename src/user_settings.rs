@@ -0,0 +1,74 @@
+// Per-user preferences, currently the language picked with `!language`. Like the guild settings
+// they're kept in memory after the first read and written back to disk on every change.
+
+use crate::i18n::Locale;
+use serde::{Deserialize, Serialize};
+use serenity::model::id::UserId;
+use std::collections::BTreeMap;
+use tokio::fs;
+use tokio::sync::Mutex;
+
+const SETTINGS_FILE: &str = "data/user_settings.json";
+
+// `None` until the settings file has been read.
+static SETTINGS: Mutex<Option<BTreeMap<u64, UserSettings>>> = Mutex::const_new(None);
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UserSettings {
+    // `None` follows the language of the server the command is used in.
+    pub language: Option<Locale>,
+}
+
+async fn load_settings() -> BTreeMap<u64, UserSettings> {
+    match fs::read_to_string(SETTINGS_FILE).await {
+        Ok(data) => serde_json::from_str(&data).unwrap_or_default(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+        Err(e) => {
+            eprintln!("Error loading user settings file: {}", e);
+            BTreeMap::new()
+        }
+    }
+}
+
+async fn save_settings(settings: &BTreeMap<u64, UserSettings>) {
+    let json_data =
+        serde_json::to_string_pretty(settings).expect("Failed to serialize user settings");
+    if let Err(dir_err) = fs::create_dir_all("data").await {
+        eprintln!("Error creating 'data' directory before saving: {}", dir_err);
+        return;
+    }
+    if let Err(e) = fs::write(SETTINGS_FILE, json_data).await {
+        eprintln!("Error saving user settings to file: {}", e);
+    }
+}
+
+pub async fn get(user_id: UserId) -> UserSettings {
+    let mut cache = SETTINGS.lock().await;
+    if cache.is_none() {
+        *cache = Some(load_settings().await);
+    }
+    cache
+        .as_ref()
+        .and_then(|settings| settings.get(&user_id.get()))
+        .cloned()
+        .unwrap_or_default()
+}
+
+// Sets or, with `None`, clears a user's language. Users left with only defaults are dropped from
+// the file.
+pub async fn set_language(user_id: UserId, language: Option<Locale>) {
+    let mut cache = SETTINGS.lock().await;
+    if cache.is_none() {
+        *cache = Some(load_settings().await);
+    }
+    let all = cache.get_or_insert_with(BTreeMap::new);
+    let mut settings = all.get(&user_id.get()).cloned().unwrap_or_default();
+    settings.language = language;
+    if settings == UserSettings::default() {
+        all.remove(&user_id.get());
+    } else {
+        all.insert(user_id.get(), settings);
+    }
+    save_settings(all).await;
+}